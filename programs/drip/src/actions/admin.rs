@@ -1,13 +1,26 @@
 use crate::errors::DripError;
+use crate::events::{
+    AdminAccepted, AdminProposed, AdminWithdrawEvent, KeeperWhitelistUpdated,
+    PositionAccountClosed, PositionReferrerUpdated, ReferrerClosed, ReferrerInitialized,
+    ReferrerSpreadUpdated, SpreadsUpdateScheduled, VaultDripScheduleUpdated, VaultInitialized,
+    VaultOracleUpdated, VaultPauseUpdated, WhitelistUpdated,
+};
 use crate::instruction_accounts::{
-    AdminWithdrawAccounts, ClosePositionAccountAccounts, InitializeVaultAccountsBumps,
-    WithdrawAAccounts,
+    AcceptAdminAccounts, AdminWithdrawAccounts, ClosePositionAccountAccounts,
+    CloseReferrerAccounts, InitializeReferrerAccounts, InitializeReferrerAccountsBumps,
+    InitializeReferrerParams, InitializeVaultAccountsBumps, ProposeAdminAccounts,
+    ProposeAdminParams, SetVaultDripScheduleAccounts, SetVaultDripScheduleParams,
+    SetVaultKeeperWhitelistAccounts, SetVaultKeeperWhitelistParams, SetVaultOracleAccounts,
+    SetVaultOracleParams, SetVaultPausedAccounts, SetVaultPausedParams, UpdateReferrerAccounts,
+    UpdateReferrerParams, UpdateReferrerSpreadAccounts, UpdateReferrerSpreadParams,
+    UpdateVaultProtoConfigSpreadsAccounts, UpdateVaultProtoConfigSpreadsParams, WithdrawAAccounts,
 };
 use crate::interactions::executor::CpiExecutor;
 use crate::interactions::transfer_token::TransferToken;
 use crate::state::{
-    Vault, MAX_SLIPPAGE_LOWER_LIMIT_EXCLUSIVE, MAX_SLIPPAGE_UPPER_LIMIT_EXCLUSIVE,
-    VAULT_SWAP_WHITELIST_SIZE,
+    OraclePrice, Vault, VaultProtoConfig, MAX_ORACLE_DEVIATION_UPPER_LIMIT_EXCLUSIVE,
+    MAX_SLIPPAGE_LOWER_LIMIT_EXCLUSIVE, MAX_SLIPPAGE_UPPER_LIMIT_EXCLUSIVE,
+    MIN_SPREAD_UPDATE_NOTICE_SECONDS, VAULT_KEEPER_WHITELIST_SIZE, VAULT_SWAP_WHITELIST_SIZE,
 };
 use crate::validate;
 use crate::ProgramError::UninitializedAccount;
@@ -17,7 +30,7 @@ use crate::{
     UpdateVaultWhitelistedSwapsAccounts, UpdateVaultWhitelistedSwapsParams,
 };
 use anchor_lang::prelude::*;
-use spl_token::state::AccountState;
+use anchor_spl::token_2022::spl_token_2022::state::AccountState;
 
 pub enum Admin<'a, 'info> {
     InitVault {
@@ -29,6 +42,33 @@ pub enum Admin<'a, 'info> {
         accounts: &'a mut UpdateVaultWhitelistedSwapsAccounts<'info>,
         params: UpdateVaultWhitelistedSwapsParams,
     },
+    SetVaultKeeperWhitelist {
+        accounts: &'a mut SetVaultKeeperWhitelistAccounts<'info>,
+        params: SetVaultKeeperWhitelistParams,
+    },
+    SetVaultDripSchedule {
+        accounts: &'a mut SetVaultDripScheduleAccounts<'info>,
+        params: SetVaultDripScheduleParams,
+    },
+    SetVaultPaused {
+        accounts: &'a mut SetVaultPausedAccounts<'info>,
+        params: SetVaultPausedParams,
+    },
+    SetVaultOracle {
+        accounts: &'a mut SetVaultOracleAccounts<'info>,
+        params: SetVaultOracleParams,
+    },
+    ProposeAdmin {
+        accounts: &'a mut ProposeAdminAccounts<'info>,
+        params: ProposeAdminParams,
+    },
+    AcceptAdmin {
+        accounts: &'a mut AcceptAdminAccounts<'info>,
+    },
+    UpdateVaultProtoConfigSpreads {
+        accounts: &'a mut UpdateVaultProtoConfigSpreadsAccounts<'info>,
+        params: UpdateVaultProtoConfigSpreadsParams,
+    },
     WithdrawA {
         accounts: &'a mut WithdrawAAccounts<'info>,
     },
@@ -38,6 +78,22 @@ pub enum Admin<'a, 'info> {
     ClosePositionAccount {
         accounts: &'a mut ClosePositionAccountAccounts<'info>,
     },
+    UpdateReferrer {
+        accounts: &'a mut UpdateReferrerAccounts<'info>,
+        params: UpdateReferrerParams,
+    },
+    InitReferrer {
+        accounts: &'a mut InitializeReferrerAccounts<'info>,
+        params: InitializeReferrerParams,
+        bumps: InitializeReferrerAccountsBumps,
+    },
+    UpdateReferrerSpread {
+        accounts: &'a mut UpdateReferrerSpreadAccounts<'info>,
+        params: UpdateReferrerSpreadParams,
+    },
+    CloseReferrer {
+        accounts: &'a mut CloseReferrerAccounts<'info>,
+    },
 }
 
 impl<'a, 'info> Validatable for Admin<'a, 'info> {
@@ -100,6 +156,118 @@ impl<'a, 'info> Validatable for Admin<'a, 'info> {
                     DripError::InvalidNumSwaps
                 );
             }
+            Admin::SetVaultKeeperWhitelist { accounts, params } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+
+                // The default pubkey marks an empty slot
+                validate!(
+                    params.whitelisted_keepers.len() <= VAULT_KEEPER_WHITELIST_SIZE
+                        && !params.whitelisted_keepers.contains(&Pubkey::default()),
+                    DripError::InvalidNumKeepers
+                );
+            }
+            Admin::SetVaultDripSchedule { accounts, params } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+
+                validate!(
+                    params.drip_execution_window < accounts.vault_proto_config.granularity,
+                    DripError::InvalidDripExecutionWindow
+                );
+            }
+            Admin::SetVaultPaused { accounts, .. } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+            }
+            Admin::SetVaultOracle { accounts, params } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+
+                validate!(
+                    accounts.token_a_mint.key() == accounts.vault.token_a_mint
+                        && accounts.token_b_mint.key() == accounts.vault.token_b_mint,
+                    DripError::InvalidMint
+                );
+
+                if let Some(oracle) = &accounts.oracle {
+                    validate!(
+                        params.max_oracle_deviation_bps > 0
+                            && params.max_oracle_deviation_bps
+                                < MAX_ORACLE_DEVIATION_UPPER_LIMIT_EXCLUSIVE,
+                        DripError::InvalidOracleDeviation
+                    );
+                    OraclePrice::load(oracle)?;
+                }
+            }
+            Admin::ProposeAdmin { accounts, .. } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+            }
+            Admin::AcceptAdmin { accounts } => {
+                validate!(
+                    accounts.vault_proto_config.pending_admin != Pubkey::default()
+                        && accounts.pending_admin.key()
+                            == accounts.vault_proto_config.pending_admin,
+                    DripError::SignerIsNotPendingAdmin
+                );
+            }
+            Admin::UpdateVaultProtoConfigSpreads { accounts, params } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                VaultProtoConfig::validate_spreads(
+                    params.token_a_drip_trigger_spread,
+                    params.token_b_withdrawal_spread,
+                    params.token_b_referral_spread,
+                )?;
+
+                // The drip trigger spread is free to go down: periods with a b_per_a_cumulative
+                // pay out exactly what was bought. Only positions opened before the accumulator
+                // still derive token b from the twap and the current spread, so lowering it
+                // lets those legacy positions withdraw slightly more than was bought for them.
+                // The program can't tell whether such positions are still open, so it's on the
+                // admin to only lower the spread once the legacy positions have closed.
+
+                let now = Clock::get().unwrap().unix_timestamp;
+                validate!(
+                    params.effective_timestamp
+                        >= now.checked_add(MIN_SPREAD_UPDATE_NOTICE_SECONDS).unwrap(),
+                    DripError::InvalidSpreadEffectiveTimestamp
+                );
+            }
             Admin::WithdrawA { accounts } => {
                 validate!(
                     accounts.admin.key() == accounts.vault_proto_config.admin,
@@ -116,6 +284,11 @@ impl<'a, 'info> Validatable for Admin<'a, 'info> {
                     DripError::IncorrectVaultTokenAccount
                 );
 
+                validate!(
+                    accounts.token_a_mint.key() == accounts.vault.token_a_mint,
+                    DripError::InvalidMint
+                );
+
                 validate!(
                     accounts.admin_token_a_account.owner == accounts.admin.key(),
                     DripError::InvalidOwner
@@ -147,6 +320,11 @@ impl<'a, 'info> Validatable for Admin<'a, 'info> {
                     DripError::IncorrectVaultTokenAccount
                 );
 
+                validate!(
+                    accounts.mint.key() == accounts.vault_token_account.mint,
+                    DripError::InvalidMint
+                );
+
                 validate!(
                     accounts.vault.drip_amount == 0,
                     DripError::CannotWithdrawAWithNonZeroDripAmount
@@ -175,6 +353,74 @@ impl<'a, 'info> Validatable for Admin<'a, 'info> {
 
                 validate!(accounts.position.is_closed, DripError::PositionIsNotClosed);
             }
+            Admin::UpdateReferrer { accounts, .. } => {
+                // Positions opened before wallet referrers can only be updated by the admin
+                let is_referrer = accounts.position.has_wallet_referrer
+                    && accounts.signer.key() == accounts.position.referrer;
+                validate!(
+                    accounts.signer.key() == accounts.vault_proto_config.admin || is_referrer,
+                    DripError::SignerIsNotAdminOrReferrer
+                );
+
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+
+                validate!(
+                    accounts.vault.key() == accounts.position.vault,
+                    DripError::InvalidVaultReference
+                );
+            }
+            Admin::InitReferrer {
+                accounts, params, ..
+            } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                // The custom referral spread replaces the proto config's referral spread
+                let (token_a_drip_trigger_spread, token_b_withdrawal_spread, _) =
+                    accounts.vault_proto_config.get_spreads();
+                VaultProtoConfig::validate_spreads(
+                    token_a_drip_trigger_spread,
+                    token_b_withdrawal_spread,
+                    params.token_b_referral_spread,
+                )?;
+            }
+            Admin::UpdateReferrerSpread { accounts, params } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.referrer_account.vault_proto_config
+                        == accounts.vault_proto_config.key(),
+                    DripError::InvalidVaultProtoConfigReference
+                );
+
+                let (token_a_drip_trigger_spread, token_b_withdrawal_spread, _) =
+                    accounts.vault_proto_config.get_spreads();
+                VaultProtoConfig::validate_spreads(
+                    token_a_drip_trigger_spread,
+                    token_b_withdrawal_spread,
+                    params.token_b_referral_spread,
+                )?;
+            }
+            Admin::CloseReferrer { accounts } => {
+                validate!(
+                    accounts.admin.key() == accounts.vault_proto_config.admin,
+                    DripError::SignerIsNotAdmin
+                );
+
+                validate!(
+                    accounts.referrer_account.vault_proto_config
+                        == accounts.vault_proto_config.key(),
+                    DripError::InvalidVaultProtoConfigReference
+                );
+            }
         }
 
         Ok(())
@@ -201,42 +447,232 @@ impl<'a, 'info> Executable for Admin<'a, 'info> {
                     accounts.vault_proto_config.granularity,
                     bumps.vault,
                 );
+
+                emit!(VaultInitialized {
+                    vault: accounts.vault.key(),
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    token_a_mint: accounts.token_a_mint.key(),
+                    token_b_mint: accounts.token_b_mint.key(),
+                    treasury_token_b_account: accounts.treasury_token_b_account.key(),
+                    max_slippage_bps: accounts.vault.max_slippage_bps,
+                    drip_activation_timestamp: accounts.vault.drip_activation_timestamp,
+                });
             }
             Admin::SetVaultSwapWhitelist { accounts, params } => {
+                let whitelisted_swaps = params.whitelisted_swaps.clone();
                 accounts
                     .vault
                     .set_whitelisted_swaps(params.whitelisted_swaps);
+
+                emit!(WhitelistUpdated {
+                    vault: accounts.vault.key(),
+                    whitelisted_swaps,
+                    limit_swaps: accounts.vault.limit_swaps,
+                });
+            }
+            Admin::SetVaultKeeperWhitelist { accounts, params } => {
+                let whitelisted_keepers = params.whitelisted_keepers.clone();
+                accounts
+                    .vault
+                    .set_whitelisted_keepers(params.whitelisted_keepers);
+
+                emit!(KeeperWhitelistUpdated {
+                    vault: accounts.vault.key(),
+                    whitelisted_keepers,
+                });
+            }
+            Admin::SetVaultDripSchedule { accounts, params } => {
+                accounts.vault.set_drip_schedule(
+                    params.drip_execution_window,
+                    params.missed_drip_policy,
+                    accounts.vault_proto_config.granularity,
+                );
+
+                emit!(VaultDripScheduleUpdated {
+                    vault: accounts.vault.key(),
+                    drip_execution_window: accounts.vault.drip_execution_window,
+                    missed_drip_policy: accounts.vault.missed_drip_policy,
+                    drip_activation_timestamp: accounts.vault.drip_activation_timestamp,
+                });
+            }
+            Admin::SetVaultPaused { accounts, params } => {
+                accounts.vault.set_paused(
+                    params.paused,
+                    params.pause_deposits,
+                    params.pause_withdrawals,
+                );
+
+                emit!(VaultPauseUpdated {
+                    vault: accounts.vault.key(),
+                    paused: accounts.vault.paused,
+                    deposits_paused: accounts.vault.deposits_paused,
+                    withdrawals_paused: accounts.vault.withdrawals_paused,
+                });
+            }
+            Admin::SetVaultOracle { accounts, params } => {
+                let (oracle, max_oracle_deviation_bps) = match &accounts.oracle {
+                    Some(oracle) => (oracle.key(), params.max_oracle_deviation_bps),
+                    None => (Pubkey::default(), 0),
+                };
+                accounts.vault.set_oracle(
+                    oracle,
+                    max_oracle_deviation_bps,
+                    accounts.token_a_mint.decimals,
+                    accounts.token_b_mint.decimals,
+                );
+
+                emit!(VaultOracleUpdated {
+                    vault: accounts.vault.key(),
+                    oracle,
+                    max_oracle_deviation_bps,
+                });
+            }
+            Admin::ProposeAdmin { accounts, params } => {
+                accounts.vault_proto_config.propose_admin(params.new_admin);
+
+                emit!(AdminProposed {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    admin: accounts.admin.key(),
+                    pending_admin: params.new_admin,
+                });
+            }
+            Admin::AcceptAdmin { accounts } => {
+                let old_admin = accounts.vault_proto_config.admin;
+                accounts.vault_proto_config.accept_admin();
+
+                emit!(AdminAccepted {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    old_admin,
+                    new_admin: accounts.vault_proto_config.admin,
+                });
+            }
+            Admin::UpdateVaultProtoConfigSpreads { accounts, params } => {
+                accounts.vault_proto_config.schedule_spreads(
+                    params.token_a_drip_trigger_spread,
+                    params.token_b_withdrawal_spread,
+                    params.token_b_referral_spread,
+                    params.effective_timestamp,
+                    params.withdrawal_spread_policy,
+                );
+
+                emit!(SpreadsUpdateScheduled {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    token_a_drip_trigger_spread: params.token_a_drip_trigger_spread,
+                    token_b_withdrawal_spread: params.token_b_withdrawal_spread,
+                    token_b_referral_spread: params.token_b_referral_spread,
+                    effective_timestamp: params.effective_timestamp,
+                    withdrawal_spread_policy: params.withdrawal_spread_policy,
+                });
             }
             Admin::WithdrawA { accounts } => {
                 let withdrawable_amount_a = accounts.vault_token_a_account.amount;
 
                 let transfer_a_to_admin = TransferToken::new(
                     &accounts.token_program,
+                    &accounts.token_a_mint,
                     &accounts.vault_token_a_account,
-                    &accounts.admin_token_a_account,
+                    &accounts.admin_token_a_account.to_account_info(),
                     &accounts.vault.to_account_info(),
                     withdrawable_amount_a,
                 );
 
                 let signer: &Vault = &accounts.vault;
                 cpi_executor.execute_all(vec![&Some(&transfer_a_to_admin)], signer)?;
+
+                emit!(AdminWithdrawEvent {
+                    vault: accounts.vault.key(),
+                    admin: accounts.admin.key(),
+                    vault_token_account: accounts.vault_token_a_account.key(),
+                    destination_token_account: accounts.admin_token_a_account.key(),
+                    amount: withdrawable_amount_a,
+                });
             }
             Admin::AdminWithdraw { accounts } => {
                 let withdrawal_amount = accounts.vault_token_account.amount;
                 let transfer = TransferToken::new(
                     &accounts.token_program,
+                    &accounts.mint,
                     &accounts.vault_token_account,
-                    &accounts.destination_token_account,
+                    &accounts.destination_token_account.to_account_info(),
                     &accounts.vault.to_account_info(),
                     withdrawal_amount,
                 );
                 let signer: &Vault = &accounts.vault;
                 cpi_executor.execute_all(vec![&Some(&transfer)], signer)?;
+
+                emit!(AdminWithdrawEvent {
+                    vault: accounts.vault.key(),
+                    admin: accounts.admin.key(),
+                    vault_token_account: accounts.vault_token_account.key(),
+                    destination_token_account: accounts.destination_token_account.key(),
+                    amount: withdrawal_amount,
+                });
             }
             Admin::ClosePositionAccount { accounts } => {
                 accounts
                     .position
                     .close(accounts.sol_destination.to_account_info())?;
+
+                emit!(PositionAccountClosed {
+                    vault: accounts.vault.key(),
+                    position: accounts.position.key(),
+                    sol_destination: accounts.sol_destination.key(),
+                });
+            }
+            Admin::UpdateReferrer { accounts, params } => {
+                let old_referrer = accounts.position.referrer;
+                accounts.position.set_referrer(params.referrer);
+
+                emit!(PositionReferrerUpdated {
+                    vault: accounts.vault.key(),
+                    position: accounts.position.key(),
+                    signer: accounts.signer.key(),
+                    old_referrer,
+                    new_referrer: params.referrer,
+                });
+            }
+            Admin::InitReferrer {
+                accounts,
+                params,
+                bumps,
+            } => {
+                accounts.referrer_account.init(
+                    accounts.vault_proto_config.key(),
+                    accounts.referrer.key(),
+                    params.token_b_referral_spread,
+                    bumps.referrer_account,
+                );
+
+                emit!(ReferrerInitialized {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    referrer: accounts.referrer.key(),
+                    token_b_referral_spread: params.token_b_referral_spread,
+                });
+            }
+            Admin::UpdateReferrerSpread { accounts, params } => {
+                let old_token_b_referral_spread = accounts.referrer_account.token_b_referral_spread;
+                accounts
+                    .referrer_account
+                    .set_token_b_referral_spread(params.token_b_referral_spread);
+
+                emit!(ReferrerSpreadUpdated {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    referrer: accounts.referrer_account.referrer,
+                    old_token_b_referral_spread,
+                    new_token_b_referral_spread: params.token_b_referral_spread,
+                });
+            }
+            Admin::CloseReferrer { accounts } => {
+                // Withdrawals fall back to the proto config's referral spread
+                accounts
+                    .referrer_account
+                    .close(accounts.sol_destination.to_account_info())?;
+
+                emit!(ReferrerClosed {
+                    vault_proto_config: accounts.vault_proto_config.key(),
+                    referrer: accounts.referrer_account.referrer,
+                    sol_destination: accounts.sol_destination.key(),
+                });
             }
         }
 
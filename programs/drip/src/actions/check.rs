@@ -0,0 +1,121 @@
+use crate::actions::withdraw::get_max_withdrawable_amount_b;
+use crate::errors::DripError;
+use crate::interactions::executor::CpiExecutor;
+use crate::math::{calculate_remaining_swaps, check_vault_invariants, VaultObligations};
+use crate::state::traits::{Executable, Validatable};
+use crate::state::{Position, VaultPeriod};
+use crate::{validate, CheckVaultInvariantsAccounts};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cmp::min;
+use std::collections::BTreeMap;
+
+pub enum Check<'a, 'info> {
+    VaultInvariants {
+        accounts: &'a CheckVaultInvariantsAccounts<'info>,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    },
+}
+
+impl<'a, 'info> Validatable for Check<'a, 'info> {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Check::VaultInvariants { accounts, .. } => {
+                validate!(
+                    accounts.vault_token_a_account.key() == accounts.vault.token_a_account,
+                    DripError::IncorrectVaultTokenAccount
+                );
+                validate!(
+                    accounts.vault_token_b_account.key() == accounts.vault.token_b_account,
+                    DripError::IncorrectVaultTokenAccount
+                );
+                validate!(
+                    accounts.vault_proto_config.key() == accounts.vault.proto_config,
+                    DripError::InvalidVaultProtoConfigReference
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, 'info> Executable for Check<'a, 'info> {
+    fn execute(self, _cpi_executor: &mut impl CpiExecutor) -> Result<()> {
+        match self {
+            Check::VaultInvariants {
+                accounts,
+                remaining_accounts,
+            } => {
+                let (positions, vault_periods) =
+                    load_positions_and_vault_periods(accounts, remaining_accounts)?;
+
+                let mut obligations = VaultObligations::default();
+                for position in positions.iter() {
+                    let i = position.drip_period_id_before_deposit;
+                    let j = min(
+                        accounts.vault.last_drip_period,
+                        i.checked_add(position.number_of_swaps).unwrap(),
+                    );
+                    let (vault_period_i, vault_period_j) =
+                        match (vault_periods.get(&i), vault_periods.get(&j)) {
+                            (Some(vault_period_i), Some(vault_period_j)) => {
+                                (vault_period_i, vault_period_j)
+                            }
+                            _ => return Err(DripError::MissingVaultPeriod.into()),
+                        };
+
+                    let max_withdrawable_amount_b = get_max_withdrawable_amount_b(
+                        vault_period_i,
+                        vault_period_j,
+                        position.periodic_drip_amount,
+                        accounts.vault_proto_config.get_token_a_swap_spread(),
+                    );
+                    obligations.add_position(
+                        calculate_remaining_swaps(i, j, position.number_of_swaps),
+                        position.periodic_drip_amount,
+                        position.get_withdrawable_amount_with_max(max_withdrawable_amount_b),
+                    );
+                }
+
+                check_vault_invariants(
+                    accounts.vault_token_a_account.amount,
+                    accounts.vault_token_b_account.amount,
+                    accounts.vault.drip_amount,
+                    &obligations,
+                )
+            }
+        }
+    }
+}
+
+// Splits the remaining accounts into the vault's positions and its vault periods keyed by period id
+fn load_positions_and_vault_periods<'info>(
+    accounts: &CheckVaultInvariantsAccounts<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<(Vec<Position>, BTreeMap<u64, VaultPeriod>)> {
+    let mut positions = vec![];
+    let mut vault_periods = BTreeMap::new();
+
+    for account in remaining_accounts.iter() {
+        let is_position = account
+            .try_borrow_data()?
+            .starts_with(&Position::DISCRIMINATOR);
+        if is_position {
+            let position: Account<Position> = Account::try_from(account)?;
+            validate!(
+                position.vault == accounts.vault.key(),
+                DripError::InvalidVaultReference
+            );
+            positions.push(position.into_inner());
+        } else {
+            let vault_period: Account<VaultPeriod> = Account::try_from(account)?;
+            validate!(
+                vault_period.vault == accounts.vault.key(),
+                DripError::InvalidVaultReference
+            );
+            vault_periods.insert(vault_period.period_id, vault_period.into_inner());
+        }
+    }
+
+    Ok((positions, vault_periods))
+}
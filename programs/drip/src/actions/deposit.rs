@@ -1,13 +1,26 @@
+use crate::actions::withdraw::{
+    execute_withdraw_b, get_withdrawable_amount_b_before_fees,
+    validate_common as validate_withdraw_common,
+};
 use crate::errors::DripError;
-use crate::instruction_accounts::{DepositAccountsBumps, DepositWithMetadataAccountsBumps};
+use crate::events::{DepositEvent, PositionIncreased};
+use crate::instruction_accounts::{
+    DepositAccountsBumps, DepositSolAccounts, DepositSolAccountsBumps,
+    DepositSolWithMetadataAccounts, DepositSolWithMetadataAccountsBumps,
+    DepositWithMetadataAccountsBumps, IncreasePositionAccounts, IncreasePositionParams,
+};
+use crate::interactions::close_account::CloseAccount;
 use crate::interactions::create_token_metadata::CreateTokenMetadata;
+use crate::interactions::create_token_metadata::MetaplexTokenMetadata;
 use crate::interactions::executor::CpiExecutor;
 use crate::interactions::mint_token::MintToken;
 use crate::interactions::set_mint_authority::SetMintAuthority;
+use crate::interactions::sync_native::SyncNative;
+use crate::interactions::transfer_lamports::TransferLamports;
 use crate::interactions::transfer_token::TransferToken;
-use crate::math::calculate_periodic_drip_amount;
+use crate::math::{calculate_periodic_drip_amount, calculate_remaining_swaps};
 use crate::state::traits::CPI;
-use crate::state::Vault;
+use crate::state::{calculate_transfer_fee, Vault, VaultPeriod};
 use crate::ProgramError::InvalidArgument;
 use crate::{
     instruction_accounts::deposit::{DepositAccounts, DepositParams, DepositWithMetadataAccounts},
@@ -15,6 +28,7 @@ use crate::{
 };
 use crate::{validate, DepositCommonAccounts};
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 const DRIP_METADATA_NAME: &str = "Drip Position";
 const DRIP_METADATA_SYMBOL: &str = "DP";
@@ -30,6 +44,22 @@ pub enum Deposit<'a, 'info> {
         params: DepositParams,
         bumps: DepositWithMetadataAccountsBumps,
     },
+    IncreasePosition {
+        accounts: &'a mut IncreasePositionAccounts<'info>,
+        params: IncreasePositionParams,
+    },
+    // Wraps the depositor's lamports into a temporary wSOL account before depositing,
+    // the account is closed afterwards so the depositor's own token accounts are untouched
+    Sol {
+        accounts: &'a mut DepositSolAccounts<'info>,
+        params: DepositParams,
+        bumps: DepositSolAccountsBumps,
+    },
+    SolWithMetadata {
+        accounts: &'a mut DepositSolWithMetadataAccounts<'info>,
+        params: DepositParams,
+        bumps: DepositSolWithMetadataAccountsBumps,
+    },
 }
 
 impl<'a, 'info> Validatable for Deposit<'a, 'info> {
@@ -37,16 +67,146 @@ impl<'a, 'info> Validatable for Deposit<'a, 'info> {
         match self {
             Deposit::WithoutMetadata {
                 accounts, params, ..
-            } => validate_common(&accounts.common, params),
+            } => {
+                validate_common(&accounts.common, params)?;
+                validate!(
+                    accounts.common.user_token_a_account.is_some(),
+                    DripError::MissingUserTokenAccount
+                );
+                Ok(())
+            }
 
             Deposit::WithMetadata {
                 accounts, params, ..
-            } => validate_common(&accounts.common, params),
+            } => {
+                validate_common(&accounts.common, params)?;
+                validate!(
+                    accounts.common.user_token_a_account.is_some(),
+                    DripError::MissingUserTokenAccount
+                );
+                Ok(())
+            }
+
+            // The temporary account's mint and authority are enforced by its init constraints
+            Deposit::Sol {
+                accounts, params, ..
+            } => {
+                validate_common(&accounts.common, params)?;
+                validate!(
+                    accounts.temporary_wsol_account.is_native(),
+                    DripError::InvalidNativeTokenAccount
+                );
+                Ok(())
+            }
+
+            Deposit::SolWithMetadata {
+                accounts, params, ..
+            } => {
+                validate_common(&accounts.common, params)?;
+                validate!(
+                    accounts.temporary_wsol_account.is_native(),
+                    DripError::InvalidNativeTokenAccount
+                );
+                Ok(())
+            }
+
+            Deposit::IncreasePosition { accounts, params } => {
+                validate_withdraw_common(&accounts.common)?;
+
+                validate!(
+                    !accounts.common.vault.deposits_paused,
+                    DripError::VaultDepositsArePaused
+                );
+
+                // only nft owner can increase their position
+                validate!(
+                    accounts.common.user_position_nft_account.owner
+                        == accounts.common.withdrawer.key(),
+                    DripError::InvalidOwner
+                );
+
+                validate!(
+                    accounts.vault_token_a_account.key() == accounts.common.vault.token_a_account,
+                    DripError::IncorrectVaultTokenAccount
+                );
+
+                validate!(
+                    accounts.token_a_mint.key() == accounts.common.vault.token_a_mint,
+                    DripError::InvalidMint
+                );
+
+                validate!(
+                    accounts.vault_period_user_expiry.vault == accounts.common.vault.key(),
+                    DripError::InvalidVaultReference
+                );
+
+                validate!(
+                    accounts.vault_period_user_expiry.period_id
+                        == accounts
+                            .common
+                            .user_position
+                            .drip_period_id_before_deposit
+                            .checked_add(accounts.common.user_position.number_of_swaps)
+                            .unwrap(),
+                    DripError::InvalidVaultPeriod
+                );
+
+                validate!(params.token_a_deposit_amount > 0, InvalidArgument);
+
+                let expected_received_amount_a = get_expected_received_amount_a(
+                    &accounts.token_a_mint.to_account_info(),
+                    params.token_a_deposit_amount,
+                )?;
+                let IncreasedPosition {
+                    number_of_swaps,
+                    periodic_drip_amount,
+                    ..
+                } = get_increased_position(
+                    accounts,
+                    expected_received_amount_a,
+                    params.number_of_swaps,
+                );
+
+                validate!(number_of_swaps > 0, DripError::NumSwapsIsZero);
+
+                validate!(
+                    periodic_drip_amount > 0,
+                    DripError::PeriodicDripAmountIsZero
+                );
+
+                validate!(
+                    accounts.vault_period_new_expiry.vault == accounts.common.vault.key(),
+                    DripError::InvalidVaultReference
+                );
+
+                validate!(
+                    accounts.vault_period_new_expiry.period_id
+                        == accounts
+                            .common
+                            .vault
+                            .last_drip_period
+                            .checked_add(number_of_swaps)
+                            .unwrap(),
+                    DripError::InvalidVaultPeriod
+                );
+
+                Ok(())
+            }
         }
     }
 }
 
 fn validate_common(accounts: &DepositCommonAccounts, params: &DepositParams) -> Result<()> {
+    validate!(
+        !accounts.vault.deposits_paused,
+        DripError::VaultDepositsArePaused
+    );
+
+    validate!(
+        accounts.vault_proto_config.key() == accounts.vault.proto_config,
+        DripError::InvalidVaultProtoConfigReference
+    );
+
     validate!(
         accounts.vault_period_end.vault == accounts.vault.key(),
         DripError::InvalidVaultReference
@@ -67,6 +227,11 @@ fn validate_common(accounts: &DepositCommonAccounts, params: &DepositParams) ->
         DripError::IncorrectVaultTokenAccount
     );
 
+    validate!(
+        accounts.token_a_mint.key() == accounts.vault.token_a_mint,
+        DripError::InvalidMint
+    );
+
     validate!(
         accounts.referrer.mint == accounts.vault.token_b_mint,
         DripError::InvalidMint
@@ -76,8 +241,12 @@ fn validate_common(accounts: &DepositCommonAccounts, params: &DepositParams) ->
 
     validate!(params.token_a_deposit_amount > 0, InvalidArgument);
 
+    let expected_received_amount_a = get_expected_received_amount_a(
+        &accounts.token_a_mint.to_account_info(),
+        params.token_a_deposit_amount,
+    )?;
     validate!(
-        calculate_periodic_drip_amount(params.token_a_deposit_amount, params.number_of_swaps) > 0,
+        calculate_periodic_drip_amount(expected_received_amount_a, params.number_of_swaps) > 0,
         DripError::PeriodicDripAmountIsZero
     );
 
@@ -91,35 +260,69 @@ impl<'a, 'info> Executable for Deposit<'a, 'info> {
                 accounts,
                 params,
                 bumps,
-            } => execute_deposit(
+            } => {
+                let user_token_a_account = accounts.common.user_token_a_account.clone().unwrap();
+                execute_deposit(
+                    &mut accounts.common,
+                    &user_token_a_account,
+                    params,
+                    bumps.common.user_position,
+                    None,
+                    cpi_executor,
+                )
+            }
+            Deposit::WithMetadata {
+                accounts,
+                params,
+                bumps,
+            } => {
+                let create_token_metadata = new_create_token_metadata(
+                    &accounts.common,
+                    &accounts.position_metadata_account,
+                    &accounts.metadata_program,
+                );
+                let user_token_a_account = accounts.common.user_token_a_account.clone().unwrap();
+
+                execute_deposit(
+                    &mut accounts.common,
+                    &user_token_a_account,
+                    params,
+                    bumps.common.user_position,
+                    Some(&create_token_metadata),
+                    cpi_executor,
+                )
+            }
+            Deposit::IncreasePosition { accounts, params } => {
+                execute_increase_position(accounts, params, cpi_executor)
+            }
+            Deposit::Sol {
+                accounts,
+                params,
+                bumps,
+            } => execute_deposit_sol(
                 &mut accounts.common,
+                &accounts.temporary_wsol_account,
                 params,
-                DepositBumps::WithoutMetadata(bumps),
+                bumps.common.user_position,
                 None,
                 cpi_executor,
             ),
-            Deposit::WithMetadata {
+            Deposit::SolWithMetadata {
                 accounts,
                 params,
                 bumps,
             } => {
-                let create_token_metadata = CreateTokenMetadata::new(
-                    &accounts.metadata_program,
-                    &accounts.common.system_program,
+                let create_token_metadata = new_create_token_metadata(
+                    &accounts.common,
                     &accounts.position_metadata_account,
-                    &accounts.common.user_position_nft_mint,
-                    &accounts.common.vault.to_account_info(),
-                    &accounts.common.depositor.to_account_info(),
-                    &accounts.common.rent,
-                    get_metadata_url(&accounts.common.user_position_nft_mint.key()),
-                    DRIP_METADATA_NAME.to_string(),
-                    DRIP_METADATA_SYMBOL.to_string(),
+                    &accounts.metadata_program,
                 );
 
-                execute_deposit(
+                execute_deposit_sol(
                     &mut accounts.common,
+                    &accounts.temporary_wsol_account,
                     params,
-                    DepositBumps::WithMetadata(bumps),
+                    bumps.common.user_position,
                     Some(&create_token_metadata),
                     cpi_executor,
                 )
@@ -128,26 +331,90 @@ impl<'a, 'info> Executable for Deposit<'a, 'info> {
     }
 }
 
-enum DepositBumps {
-    WithoutMetadata(DepositAccountsBumps),
-    WithMetadata(DepositWithMetadataAccountsBumps),
+fn new_create_token_metadata<'info>(
+    accounts: &DepositCommonAccounts<'info>,
+    position_metadata_account: &AccountInfo<'info>,
+    metadata_program: &Program<'info, MetaplexTokenMetadata>,
+) -> CreateTokenMetadata<'info> {
+    CreateTokenMetadata::new(
+        metadata_program,
+        &accounts.system_program,
+        position_metadata_account,
+        &accounts.user_position_nft_mint,
+        &accounts.vault.to_account_info(),
+        &accounts.depositor.to_account_info(),
+        &accounts.rent,
+        get_metadata_url(&accounts.user_position_nft_mint.key()),
+        DRIP_METADATA_NAME.to_string(),
+        DRIP_METADATA_SYMBOL.to_string(),
+    )
 }
 
-fn execute_deposit(
-    accounts: &mut DepositCommonAccounts,
+fn execute_deposit_sol<'info>(
+    accounts: &mut DepositCommonAccounts<'info>,
+    temporary_wsol_account: &InterfaceAccount<'info, TokenAccount>,
     params: DepositParams,
-    bumps: DepositBumps,
+    user_position_bump: u8,
+    create_token_metadata: Option<&dyn CPI>,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    let wrap_sol = TransferLamports::new(
+        &accounts.system_program,
+        &accounts.depositor.to_account_info(),
+        &temporary_wsol_account.to_account_info(),
+        params.token_a_deposit_amount,
+    );
+    let sync_native = SyncNative::new(&accounts.token_a_program, temporary_wsol_account);
+    let signer: &Vault = &accounts.vault;
+    cpi_executor.execute_all(vec![&Some(&wrap_sol), &Some(&sync_native)], signer)?;
+
+    execute_deposit(
+        accounts,
+        temporary_wsol_account,
+        params,
+        user_position_bump,
+        create_token_metadata,
+        cpi_executor,
+    )?;
+
+    // Returns the account's rent to the depositor
+    let close_temporary_wsol_account = CloseAccount::new(
+        &accounts.token_a_program,
+        temporary_wsol_account,
+        &accounts.depositor,
+        &accounts.depositor,
+    );
+    let signer: &Vault = &accounts.vault;
+    cpi_executor.execute_all(vec![&Some(&close_temporary_wsol_account)], signer)
+}
+
+fn execute_deposit<'info>(
+    accounts: &mut DepositCommonAccounts<'info>,
+    user_token_a_account: &InterfaceAccount<'info, TokenAccount>,
+    params: DepositParams,
+    user_position_bump: u8,
     create_token_metadata: Option<&dyn CPI>,
     cpi_executor: &mut impl CpiExecutor,
 ) -> Result<()> {
     let token_transfer = TransferToken::new(
-        &accounts.token_program,
-        &accounts.user_token_a_account,
-        &accounts.vault_token_a_account,
+        &accounts.token_a_program,
+        &accounts.token_a_mint,
+        user_token_a_account,
+        &accounts.vault_token_a_account.to_account_info(),
         &accounts.depositor.to_account_info(),
         // &accounts.vault.to_account_info(),
         params.token_a_deposit_amount,
     );
+    let received_amount_a = transfer_token_a_to_vault(
+        &token_transfer,
+        &accounts.vault,
+        &mut accounts.vault_token_a_account,
+        cpi_executor,
+    )?;
+    validate!(
+        calculate_periodic_drip_amount(received_amount_a, params.number_of_swaps) > 0,
+        DripError::PeriodicDripAmountIsZero
+    );
 
     let mint_position_nft = MintToken::new(
         &accounts.token_program,
@@ -163,12 +430,13 @@ fn execute_deposit(
         &accounts.vault.to_account_info(),
         None,
     );
-    let user_position_bump = match bumps {
-        DepositBumps::WithoutMetadata(bumps) => bumps.common.user_position,
-        DepositBumps::WithMetadata(bumps) => bumps.common.user_position,
-    };
     /* STATE UPDATES (EFFECTS) */
-    update_state(accounts, params, user_position_bump);
+    update_state(
+        accounts,
+        received_amount_a,
+        params.number_of_swaps,
+        user_position_bump,
+    );
 
     /* MANUAL CPI (INTERACTIONS) */
 
@@ -176,7 +444,6 @@ fn execute_deposit(
 
     cpi_executor.execute_all(
         vec![
-            &Some(&token_transfer),
             &Some(&mint_position_nft),
             &create_token_metadata,
             &Some(&revoke_position_nft_auth),
@@ -184,17 +451,33 @@ fn execute_deposit(
         signer,
     )?;
 
+    emit!(DepositEvent {
+        vault: accounts.vault.key(),
+        position: accounts.user_position.key(),
+        position_nft_mint: accounts.user_position_nft_mint.key(),
+        depositor: accounts.depositor.key(),
+        referrer: accounts.referrer.key(),
+        token_a_deposit_amount: accounts.user_position.deposited_token_a_amount,
+        number_of_swaps: accounts.user_position.number_of_swaps,
+        periodic_drip_amount: accounts.user_position.periodic_drip_amount,
+        drip_period_id_before_deposit: accounts.user_position.drip_period_id_before_deposit,
+    });
+
     Ok(())
 }
 
 fn update_state(
     accounts: &mut DepositCommonAccounts,
-    params: DepositParams,
+    token_a_deposit_amount: u64,
+    number_of_swaps: u64,
     user_position_bump: u8,
 ) {
     let periodic_drip_amount =
-        calculate_periodic_drip_amount(params.token_a_deposit_amount, params.number_of_swaps);
+        calculate_periodic_drip_amount(token_a_deposit_amount, number_of_swaps);
 
+    accounts
+        .vault
+        .restart_drip_schedule_if_idle(accounts.vault_proto_config.granularity);
     accounts.vault.increase_drip_amount(periodic_drip_amount);
 
     accounts
@@ -204,13 +487,196 @@ fn update_state(
     accounts.user_position.init(
         accounts.vault.key(),
         accounts.user_position_nft_mint.key(),
-        accounts.referrer.key(),
-        params.token_a_deposit_amount,
+        // Stored as a wallet so referral fees follow the referrer's token b ATA
+        accounts.referrer.owner,
+        token_a_deposit_amount,
         accounts.vault.last_drip_period,
-        params.number_of_swaps,
+        number_of_swaps,
         periodic_drip_amount,
         user_position_bump,
     );
+
+    // Recorded on every new position so the AtDeposit policy can't be opted out of
+    let (_, token_b_withdrawal_spread, token_b_referral_spread) =
+        accounts.vault_proto_config.get_spreads();
+    accounts
+        .user_position
+        .record_spreads(token_b_withdrawal_spread, token_b_referral_spread);
+}
+
+fn execute_increase_position(
+    accounts: &mut IncreasePositionAccounts,
+    params: IncreasePositionParams,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    execute_increase_position_at(accounts, params, Clock::get()?.unix_timestamp, cpi_executor)
+}
+
+fn execute_increase_position_at(
+    accounts: &mut IncreasePositionAccounts,
+    params: IncreasePositionParams,
+    timestamp: i64,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    // Settle the token B accrued so far, the position's drip schedule is restarted below
+    if get_withdrawable_amount_b_before_fees(&accounts.common) > 0 {
+        execute_withdraw_b(&mut accounts.common, cpi_executor)?;
+    }
+
+    let token_transfer = TransferToken::new(
+        &accounts.token_a_program,
+        &accounts.token_a_mint,
+        &accounts.user_token_a_account,
+        &accounts.vault_token_a_account.to_account_info(),
+        &accounts.common.withdrawer.to_account_info(),
+        params.token_a_deposit_amount,
+    );
+    let received_amount_a = transfer_token_a_to_vault(
+        &token_transfer,
+        &accounts.common.vault,
+        &mut accounts.vault_token_a_account,
+        cpi_executor,
+    )?;
+
+    /* COMPUTE (CHECKS) */
+    let IncreasedPosition {
+        is_expired,
+        number_of_swaps,
+        periodic_drip_amount,
+    } = get_increased_position(accounts, received_amount_a, params.number_of_swaps);
+    validate!(
+        periodic_drip_amount > 0,
+        DripError::PeriodicDripAmountIsZero
+    );
+
+    /* STATE UPDATES (EFFECTS) */
+    let old_periodic_drip_amount = if is_expired {
+        // the dar has already been reduced for this position
+        0
+    } else {
+        accounts.common.user_position.periodic_drip_amount
+    };
+    accounts.common.vault.restart_drip_schedule_if_idle_at(
+        timestamp,
+        accounts.common.vault_proto_config.granularity,
+    );
+    reschedule_drip_amount(
+        &mut accounts.common.vault,
+        &mut accounts.vault_period_user_expiry,
+        &mut accounts.vault_period_new_expiry,
+        old_periodic_drip_amount,
+        periodic_drip_amount,
+    );
+
+    let last_drip_period = accounts.common.vault.last_drip_period;
+    let (_, token_b_withdrawal_spread, token_b_referral_spread) =
+        accounts.common.vault_proto_config.get_spreads_at(timestamp);
+    let user_position = &mut accounts.common.user_position;
+    user_position.increase_deposited_amount(received_amount_a);
+    user_position.reset_drip_schedule(last_drip_period, number_of_swaps, periodic_drip_amount);
+    // The top up is a new deposit, so it is made under the spreads currently in effect
+    user_position.record_spreads(token_b_withdrawal_spread, token_b_referral_spread);
+
+    emit!(PositionIncreased {
+        vault: accounts.common.vault.key(),
+        position: accounts.common.user_position.key(),
+        token_a_deposit_amount: received_amount_a,
+        number_of_swaps,
+        periodic_drip_amount,
+        drip_period_id_before_deposit: last_drip_period,
+    });
+
+    Ok(())
+}
+
+struct IncreasedPosition {
+    pub is_expired: bool,
+    pub number_of_swaps: u64,
+    pub periodic_drip_amount: u64,
+}
+
+fn get_increased_position(
+    accounts: &IncreasePositionAccounts,
+    token_a_deposit_amount: u64,
+    extra_number_of_swaps: u64,
+) -> IncreasedPosition {
+    let user_position = &accounts.common.user_position;
+    let remaining_swaps = calculate_remaining_swaps(
+        user_position.drip_period_id_before_deposit,
+        accounts.common.vault_period_j.period_id,
+        user_position.number_of_swaps,
+    );
+    let remaining_amount_a = remaining_swaps
+        .checked_mul(user_position.periodic_drip_amount)
+        .unwrap();
+
+    let number_of_swaps = remaining_swaps.checked_add(extra_number_of_swaps).unwrap();
+    let periodic_drip_amount = if number_of_swaps > 0 {
+        calculate_periodic_drip_amount(
+            remaining_amount_a
+                .checked_add(token_a_deposit_amount)
+                .unwrap(),
+            number_of_swaps,
+        )
+    } else {
+        0
+    };
+
+    IncreasedPosition {
+        is_expired: remaining_swaps == 0,
+        number_of_swaps,
+        periodic_drip_amount,
+    }
+}
+
+// Token A that reaches the vault once the transfer fee is withheld
+fn get_expected_received_amount_a(token_a_mint: &AccountInfo, amount: u64) -> Result<u64> {
+    Ok(amount
+        .checked_sub(calculate_transfer_fee(token_a_mint, amount)?)
+        .unwrap())
+}
+
+// Positions are credited with what the vault actually received, which is less than the
+// transferred amount if token a charges a transfer fee
+fn transfer_token_a_to_vault<'info>(
+    token_transfer: &TransferToken<'info>,
+    vault: &Account<'info, Vault>,
+    vault_token_a_account: &mut InterfaceAccount<'info, TokenAccount>,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<u64> {
+    let balance_before = vault_token_a_account.amount;
+
+    let signer: &Vault = vault;
+    cpi_executor.execute_all(vec![&Some(token_transfer)], signer)?;
+
+    vault_token_a_account.reload()?;
+    Ok(vault_token_a_account
+        .amount
+        .checked_sub(balance_before)
+        .unwrap())
+}
+
+// Moves a position's periodic drip amount from its old expiry period to its new expiry period
+pub(crate) fn reschedule_drip_amount(
+    vault: &mut Vault,
+    vault_period_old_expiry: &mut Account<VaultPeriod>,
+    vault_period_new_expiry: &mut Account<VaultPeriod>,
+    old_periodic_drip_amount: u64,
+    new_periodic_drip_amount: u64,
+) {
+    vault.decrease_drip_amount(old_periodic_drip_amount);
+    vault.increase_drip_amount(new_periodic_drip_amount);
+
+    if vault_period_old_expiry.key() == vault_period_new_expiry.key() {
+        // Both accounts are written back on exit, so they need to end up with the same state
+        vault_period_old_expiry.decrease_drip_amount_to_reduce(old_periodic_drip_amount);
+        vault_period_old_expiry.increase_drip_amount_to_reduce(new_periodic_drip_amount);
+        vault_period_new_expiry.decrease_drip_amount_to_reduce(old_periodic_drip_amount);
+        vault_period_new_expiry.increase_drip_amount_to_reduce(new_periodic_drip_amount);
+    } else {
+        vault_period_old_expiry.decrease_drip_amount_to_reduce(old_periodic_drip_amount);
+        vault_period_new_expiry.increase_drip_amount_to_reduce(new_periodic_drip_amount);
+    }
 }
 
 fn get_metadata_url(position_nft_mint_pubkey: &Pubkey) -> String {
@@ -219,3 +685,219 @@ fn get_metadata_url(position_nft_mint_pubkey: &Pubkey) -> String {
         position_nft_mint_pubkey
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::DripError::{InvalidVaultPeriod, NumSwapsIsZero, PeriodicDripAmountIsZero};
+    use crate::interactions::executor::test::TestCpiExecutor;
+    use crate::interactions::executor::CpiIdentifier;
+    use crate::state::traits::PDA;
+    use crate::test::fixtures::{
+        new_vault_period, AccountFixture, UpdatePositionFixtures, TOKEN_A_MINT, VAULT,
+        VAULT_TOKEN_A_ACCOUNT,
+    };
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::Token;
+    use test_case::test_case;
+
+    const NUMBER_OF_SWAPS: u64 = 4;
+    const PERIODIC_DRIP_AMOUNT: u64 = 10;
+
+    // Credits the vault's token a account as if the user's transfer had landed
+    struct TestIncreasePositionCpiExecutor<'info> {
+        base_cpi_executor: TestCpiExecutor,
+        vault_token_a_account: AccountInfo<'info>,
+        token_a_to_receive: u64,
+    }
+
+    impl<'info> CpiExecutor for TestIncreasePositionCpiExecutor<'info> {
+        fn execute_all(&mut self, cpis: Vec<&Option<&dyn CPI>>, signer: &dyn PDA) -> Result<()> {
+            self.base_cpi_executor.execute_all(cpis, signer)?;
+
+            let mut data = self.vault_token_a_account.try_borrow_mut_data()?;
+            let mut vault_token_a_account = spl_token::state::Account::unpack(&data)?;
+            vault_token_a_account.amount += self.token_a_to_receive;
+            spl_token::state::Account::pack(vault_token_a_account, &mut data)?;
+
+            Ok(())
+        }
+    }
+
+    #[test_case(4, 40, 0, 4, Err(NumSwapsIsZero.into()); "Returns error for a position that just swapped for the last time without extra swaps")]
+    #[test_case(5, 30, 0, 5, Err(NumSwapsIsZero.into()); "Returns error for a position expired before the last drip without extra swaps")]
+    #[test_case(5, 2, 3, 8, Err(PeriodicDripAmountIsZero.into()); "Returns error when the deposit is too small for the extra swaps")]
+    #[test_case(2, 40, 2, 4, Err(InvalidVaultPeriod.into()); "Returns error when the new expiry period doesn't match the new schedule")]
+    #[test_case(2, 40, 2, 6, Ok(()); "Returns ok for an active position")]
+    #[test_case(2, 20, 0, 4, Ok(()); "Returns ok for a top up without extra swaps")]
+    #[test_case(5, 30, 3, 8, Ok(()); "Returns ok for an expired position with extra swaps")]
+    fn increase_position_validate(
+        last_drip_period: u64,
+        token_a_deposit_amount: u64,
+        number_of_swaps: u64,
+        new_expiry_period_id: u64,
+        expected_res: Result<()>,
+    ) {
+        let mut fixtures = UpdatePositionFixtures::new(
+            NUMBER_OF_SWAPS,
+            PERIODIC_DRIP_AMOUNT,
+            last_drip_period,
+            new_expiry_period_id,
+        );
+        let account_infos = fixtures.to_account_infos();
+        let accounts = &mut account_infos.to_increase_position_accounts();
+
+        let increase_position_action = Deposit::IncreasePosition {
+            accounts,
+            params: IncreasePositionParams {
+                token_a_deposit_amount,
+                number_of_swaps,
+            },
+        };
+        assert_eq!(increase_position_action.validate(), expected_res);
+    }
+
+    #[test_case(2, 40, 2, 4, 15, 0, 15; "Spreads the deposit and the remaining token a over the extended schedule")]
+    #[test_case(2, 20, 0, 2, 20, 20, 20; "Reschedules the drip amount within the same vault period when no swaps are added")]
+    #[test_case(4, 20, 2, 2, 10, 10, 10; "Doesn't reduce the dar again for a position that just swapped for the last time")]
+    #[test_case(5, 30, 3, 3, 10, 10, 10; "Restarts an expired position from the last drip period")]
+    fn increase_position_happy_path(
+        last_drip_period: u64,
+        token_a_deposit_amount: u64,
+        extra_number_of_swaps: u64,
+        expected_number_of_swaps: u64,
+        expected_periodic_drip_amount: u64,
+        expected_user_expiry_dar: u64,
+        expected_new_expiry_dar: u64,
+    ) {
+        let new_expiry_period_id = last_drip_period + expected_number_of_swaps;
+        let mut fixtures = UpdatePositionFixtures::new(
+            NUMBER_OF_SWAPS,
+            PERIODIC_DRIP_AMOUNT,
+            last_drip_period,
+            new_expiry_period_id,
+        );
+        let account_infos = fixtures.to_account_infos();
+        let accounts = &mut account_infos.to_increase_position_accounts();
+        let user_token_a_account = accounts.user_token_a_account.key();
+        let withdrawer = accounts.common.withdrawer.key();
+        let mut cpi_executor = TestIncreasePositionCpiExecutor {
+            base_cpi_executor: TestCpiExecutor {
+                cpi_calls: vec![],
+                signer: None,
+            },
+            vault_token_a_account: account_infos.vault_token_a_account.clone(),
+            token_a_to_receive: token_a_deposit_amount,
+        };
+
+        let increase_position_action = Deposit::IncreasePosition {
+            accounts,
+            params: IncreasePositionParams {
+                token_a_deposit_amount,
+                number_of_swaps: extra_number_of_swaps,
+            },
+        };
+        assert_eq!(increase_position_action.validate(), Ok(()));
+        execute_increase_position_at(
+            accounts,
+            IncreasePositionParams {
+                token_a_deposit_amount,
+                number_of_swaps: extra_number_of_swaps,
+            },
+            0,
+            &mut cpi_executor,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cpi_executor.base_cpi_executor.cpi_calls,
+            vec![CpiIdentifier::TransferToken {
+                token_program: Token::id(),
+                mint: TOKEN_A_MINT.key,
+                from: user_token_a_account,
+                to: VAULT_TOKEN_A_ACCOUNT.key,
+                authority: withdrawer,
+                amount: token_a_deposit_amount,
+            }]
+        );
+
+        let user_position = &accounts.common.user_position;
+        assert_eq!(
+            user_position.drip_period_id_before_deposit,
+            last_drip_period
+        );
+        assert_eq!(user_position.number_of_swaps, expected_number_of_swaps);
+        assert_eq!(
+            user_position.periodic_drip_amount,
+            expected_periodic_drip_amount
+        );
+        assert_eq!(
+            user_position.deposited_token_a_amount,
+            NUMBER_OF_SWAPS * PERIODIC_DRIP_AMOUNT + token_a_deposit_amount
+        );
+        assert_eq!(user_position.withdrawn_token_b_amount, 0);
+
+        assert_eq!(
+            accounts.common.vault.drip_amount,
+            expected_periodic_drip_amount
+        );
+        assert_eq!(
+            accounts.vault_period_user_expiry.dar,
+            expected_user_expiry_dar
+        );
+        assert_eq!(
+            accounts.vault_period_new_expiry.dar,
+            expected_new_expiry_dar
+        );
+    }
+
+    #[test_case(4, 6, 10, 15, 0, 15; "Moves the drip amount to the new expiry period")]
+    #[test_case(4, 4, 10, 15, 15, 15; "Updates both accounts when the expiry period doesn't change")]
+    #[test_case(4, 6, 0, 15, 10, 15; "Only adds the new drip amount for an expired position")]
+    fn reschedule_drip_amount_tests(
+        old_expiry_period_id: u64,
+        new_expiry_period_id: u64,
+        old_periodic_drip_amount: u64,
+        new_periodic_drip_amount: u64,
+        expected_old_expiry_dar: u64,
+        expected_new_expiry_dar: u64,
+    ) {
+        let mut vault = Vault {
+            drip_amount: 20,
+            ..Vault::try_deserialize(&mut VAULT.data.as_slice()).unwrap()
+        };
+        let mut vault_period_old_expiry_fixture =
+            AccountFixture::new_drip_account(new_vault_period(old_expiry_period_id, 10), None);
+        let mut vault_period_new_expiry_fixture =
+            AccountFixture::new_drip_account(new_vault_period(new_expiry_period_id, 0), None);
+        let vault_period_old_expiry_account_info =
+            &vault_period_old_expiry_fixture.to_account_info();
+        let vault_period_new_expiry_account_info =
+            &vault_period_new_expiry_fixture.to_account_info();
+        let mut vault_period_old_expiry =
+            Account::try_from(vault_period_old_expiry_account_info).unwrap();
+        // Both accounts share the same account info when the expiry period doesn't change
+        let mut vault_period_new_expiry =
+            Account::try_from(if new_expiry_period_id == old_expiry_period_id {
+                vault_period_old_expiry_account_info
+            } else {
+                vault_period_new_expiry_account_info
+            })
+            .unwrap();
+
+        reschedule_drip_amount(
+            &mut vault,
+            &mut vault_period_old_expiry,
+            &mut vault_period_new_expiry,
+            old_periodic_drip_amount,
+            new_periodic_drip_amount,
+        );
+
+        assert_eq!(
+            vault.drip_amount,
+            20 - old_periodic_drip_amount + new_periodic_drip_amount
+        );
+        assert_eq!(vault_period_old_expiry.dar, expected_old_expiry_dar);
+        assert_eq!(vault_period_new_expiry.dar, expected_new_expiry_dar);
+    }
+}
@@ -1,21 +1,51 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::DripError::{
-    DuplicateDripError, IncorrectVaultTokenAccount, InvalidSwapAccount, InvalidVaultPeriod,
-    InvalidVaultProtoConfigReference, InvalidVaultReference, PeriodicDripAmountIsZero,
+    DripExecutionWindowMissed, InvalidCatchUpDrip, InvalidDripFeeTokenAccount, InvalidDripSplit,
+    InvalidIntermediateTokenAccount, InvalidMinimumOut, InvalidOracleAccount, InvalidOraclePrice,
+    KeeperNotWhitelisted, MissingOraclePrice, OraclePriceDeviationExceeded, StaleOraclePrice,
+    UnsupportedCurveType, UnsupportedTokenProgram,
+};
+use crate::errors::DripError::{
+    DuplicateDripError, IncorrectVaultTokenAccount, InvalidMint, InvalidSwapAccount,
+    InvalidVaultPeriod, InvalidVaultProtoConfigReference, InvalidVaultReference,
+    PeriodicDripAmountIsZero, VaultIsPaused, VaultTokenAccountModified,
 };
 
 use crate::errors::DripError;
+use crate::events::DripEvent;
 use crate::interactions::executor::CpiExecutor;
+use crate::interactions::swap_aggregator::SwapAggregator;
+use crate::interactions::swap_raydium::{
+    get_raydium_amount_in_after_fees, get_raydium_reserves, SwapRaydium, SwapRaydiumAccounts,
+};
 use crate::interactions::swap_spl_token_swap::SwapSPLTokenSwap;
+use crate::interactions::swap_two_hop::SwapTwoHop;
 use crate::interactions::transfer_token::TransferToken;
-use crate::math::{calculate_spread_amount, calculate_sqrt_price_limit};
-use crate::state::Vault;
+use crate::math::{
+    calculate_constant_product_minimum_out, calculate_minimum_out, calculate_oracle_price_x64,
+    calculate_spread_amount, calculate_sqrt_price_limit, calculate_whirlpool_minimum_out,
+    compute_price, is_price_within_deviation,
+};
+use crate::state::{
+    calculate_inverse_transfer_fee, KeeperRewardType, MissedDripPolicy, OraclePrice, Vault,
+    VaultPeriod,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_token_swap::curve::base::CurveType;
+use spl_token_swap::state::SwapVersion;
+use std::convert::TryFrom;
+use whirlpool::state::Whirlpool;
 
 use crate::interactions::swap_orca_whirlpool::SwapOrcaWhirlpool;
 
 use crate::{
-    instruction_accounts::{DripOrcaWhirlpoolAccounts, DripSPLTokenSwapAccounts},
+    instruction_accounts::{
+        DripAggregatorAccounts, DripAggregatorParams, DripOrcaWhirlpoolAccounts,
+        DripOrcaWhirlpoolTwoHopAccounts, DripRaydiumAccounts, DripSPLTokenSwapAccounts,
+        DripSplitAccounts, DripSplitLeg, DripSplitParams, SwapVenue, MAX_CATCH_UP_PERIODS,
+        MAX_DRIP_SPLIT_LEGS,
+    },
     state::traits::{Executable, Validatable},
     validate, DripCommonAccounts, CPI,
 };
@@ -27,22 +57,179 @@ pub enum Drip<'a, 'info> {
     OrcaWhirlpool {
         accounts: &'a mut DripOrcaWhirlpoolAccounts<'info>,
     },
+    OrcaWhirlpoolCatchUp {
+        accounts: &'a mut DripOrcaWhirlpoolAccounts<'info>,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    },
+    OrcaWhirlpoolTwoHop {
+        accounts: &'a mut DripOrcaWhirlpoolTwoHopAccounts<'info>,
+    },
+    Raydium {
+        accounts: &'a mut DripRaydiumAccounts<'info>,
+    },
+    Split {
+        accounts: &'a mut DripSplitAccounts<'info>,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        params: DripSplitParams,
+    },
+    Aggregator {
+        accounts: &'a mut DripAggregatorAccounts<'info>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        params: DripAggregatorParams,
+    },
 }
 
 impl<'a, 'info> Validatable for Drip<'a, 'info> {
     fn validate(&self) -> Result<()> {
         match self {
             Drip::SPLTokenSwap { accounts, .. } => {
-                validate_common(&accounts.common, &accounts.swap.key())
+                validate_classic_token_program(&accounts.common)?;
+                validate_common(
+                    &accounts.common,
+                    &accounts.swap.key(),
+                    accounts.price_oracle.as_ref(),
+                )
             }
             Drip::OrcaWhirlpool { accounts, .. } => {
-                validate_common(&accounts.common, &accounts.whirlpool.key())
+                validate_classic_token_program(&accounts.common)?;
+                validate_common(
+                    &accounts.common,
+                    &accounts.whirlpool.key(),
+                    accounts.price_oracle.as_ref(),
+                )
+            }
+            Drip::OrcaWhirlpoolCatchUp {
+                accounts,
+                remaining_accounts,
+            } => {
+                let catch_up_vault_periods = load_catch_up_vault_periods(remaining_accounts)?;
+                validate_catch_up(&accounts.common, &catch_up_vault_periods)?;
+                validate_classic_token_program(&accounts.common)?;
+                validate_common(
+                    &accounts.common,
+                    &accounts.whirlpool.key(),
+                    accounts.price_oracle.as_ref(),
+                )
+            }
+            Drip::OrcaWhirlpoolTwoHop { accounts, .. } => {
+                let vault = &accounts.common.vault;
+                validate!(
+                    !vault.limit_swaps
+                        || vault
+                            .whitelisted_swaps
+                            .contains(&accounts.whirlpool_two.key()),
+                    InvalidSwapAccount
+                );
+
+                let intermediate_token_account = &accounts.intermediate_token_account;
+                validate!(
+                    intermediate_token_account.owner == vault.key()
+                        && intermediate_token_account.mint != vault.token_a_mint
+                        && intermediate_token_account.mint != vault.token_b_mint
+                        && intermediate_token_account.delegate.is_none()
+                        && intermediate_token_account.close_authority.is_none(),
+                    InvalidIntermediateTokenAccount
+                );
+                validate!(
+                    *intermediate_token_account.to_account_info().owner == anchor_spl::token::ID,
+                    UnsupportedTokenProgram
+                );
+                validate_classic_token_program(&accounts.common)?;
+
+                validate_common(
+                    &accounts.common,
+                    &accounts.whirlpool_one.key(),
+                    accounts.price_oracle.as_ref(),
+                )
+            }
+            Drip::Raydium { accounts, .. } => {
+                validate_classic_token_program(&accounts.common)?;
+                validate_common(
+                    &accounts.common,
+                    &accounts.amm.key(),
+                    accounts.price_oracle.as_ref(),
+                )
+            }
+            Drip::Split {
+                accounts,
+                remaining_accounts,
+                params,
+            } => {
+                validate!(
+                    !params.legs.is_empty() && params.legs.len() <= MAX_DRIP_SPLIT_LEGS,
+                    InvalidDripSplit
+                );
+
+                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common, &[]);
+                let total_amount_in = params
+                    .legs
+                    .iter()
+                    .try_fold(0_u64, |total, leg| total.checked_add(leg.amount_in));
+                validate!(
+                    total_amount_in == Some(swap_amount)
+                        && params.legs.iter().all(|leg| leg.amount_in > 0),
+                    InvalidDripSplit
+                );
+
+                let leg_accounts = get_split_leg_accounts(&params.legs, remaining_accounts)?;
+                let vault = &accounts.common.vault;
+                for leg_accounts in leg_accounts.iter() {
+                    validate!(
+                        !vault.limit_swaps || vault.whitelisted_swaps.contains(leg_accounts[1].key),
+                        InvalidSwapAccount
+                    );
+                }
+                validate_classic_token_program(&accounts.common)?;
+
+                validate_common(
+                    &accounts.common,
+                    leg_accounts[0][1].key,
+                    accounts.price_oracle.as_ref(),
+                )
+            }
+            Drip::Aggregator { accounts, .. } => {
+                // The router is handed the vault's signature, so it must always be whitelisted
+                validate!(
+                    accounts
+                        .common
+                        .vault
+                        .whitelisted_swaps
+                        .contains(&accounts.router_program.key()),
+                    InvalidSwapAccount
+                );
+                // The keeper picks the route, the oracle is the only price it can't influence
+                validate!(
+                    accounts.common.vault.has_oracle() && accounts.price_oracle.is_some(),
+                    MissingOraclePrice
+                );
+                validate_common(
+                    &accounts.common,
+                    &accounts.router_program.key(),
+                    accounts.price_oracle.as_ref(),
+                )
             }
         }
     }
 }
 
-fn validate_common(accounts: &DripCommonAccounts, swap: &Pubkey) -> Result<()> {
+// SPL token-swap, Raydium v4 and Whirlpool's v1 swap only take the classic token program,
+// the aggregator is the only venue token-2022 vaults can drip through
+fn validate_classic_token_program(accounts: &DripCommonAccounts) -> Result<()> {
+    validate!(
+        *accounts.token_a_mint.to_account_info().owner == anchor_spl::token::ID
+            && *accounts.token_b_mint.to_account_info().owner == anchor_spl::token::ID
+            && accounts.token_a_program.key() == anchor_spl::token::ID
+            && accounts.token_b_program.key() == anchor_spl::token::ID,
+        UnsupportedTokenProgram
+    );
+    Ok(())
+}
+
+fn validate_common(
+    accounts: &DripCommonAccounts,
+    swap: &Pubkey,
+    oracle: Option<&UncheckedAccount>,
+) -> Result<()> {
     validate!(
         accounts.vault_proto_config.key() == accounts.vault.proto_config,
         InvalidVaultProtoConfigReference
@@ -68,6 +255,12 @@ fn validate_common(accounts: &DripCommonAccounts, swap: &Pubkey) -> Result<()> {
         IncorrectVaultTokenAccount
     );
 
+    validate!(
+        accounts.token_a_mint.key() == accounts.vault.token_a_mint
+            && accounts.token_b_mint.key() == accounts.vault.token_b_mint,
+        InvalidMint
+    );
+
     validate!(
         accounts.last_vault_period.period_id == accounts.vault.last_drip_period,
         InvalidVaultPeriod
@@ -79,12 +272,41 @@ fn validate_common(accounts: &DripCommonAccounts, swap: &Pubkey) -> Result<()> {
         InvalidVaultPeriod
     );
 
+    validate!(
+        accounts
+            .vault
+            .is_keeper_allowed(&accounts.drip_trigger_source.key()),
+        KeeperNotWhitelisted
+    );
+
+    validate!(!accounts.vault.paused, VaultIsPaused);
     validate!(accounts.vault.drip_amount > 0, PeriodicDripAmountIsZero);
     validate!(accounts.vault.is_drip_activated(), DuplicateDripError);
+    validate!(
+        accounts
+            .vault
+            .is_within_drip_execution_window(accounts.vault_proto_config.granularity),
+        DripExecutionWindowMissed
+    );
     validate!(
         !accounts.vault.limit_swaps || accounts.vault.whitelisted_swaps.contains(swap),
         InvalidSwapAccount
     );
+    validate!(
+        !accounts.vault.has_oracle()
+            || oracle.map(|oracle| oracle.key()) == Some(accounts.vault.oracle),
+        InvalidOracleAccount
+    );
+    validate!(
+        match accounts.vault_proto_config.keeper_reward_type {
+            KeeperRewardType::TokenA =>
+                accounts.drip_fee_token_account.mint == accounts.vault.token_a_mint,
+            KeeperRewardType::TokenB =>
+                accounts.drip_fee_token_account.mint == accounts.vault.token_b_mint,
+            KeeperRewardType::Lamports => true,
+        },
+        InvalidDripFeeTokenAccount
+    );
 
     Ok(())
 }
@@ -93,10 +315,17 @@ impl<'a, 'info> Executable for Drip<'a, 'info> {
     fn execute(self, cpi_executor: &mut impl CpiExecutor) -> Result<()> {
         match self {
             Drip::SPLTokenSwap { accounts } => {
-                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common);
+                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common, &[]);
+                let minimum_out = get_spl_token_swap_minimum_out(
+                    &accounts.swap,
+                    &accounts.common.swap_token_a_account,
+                    &accounts.common.swap_token_b_account,
+                    swap_amount,
+                    accounts.common.vault.max_slippage_bps,
+                )?;
                 let swap = SwapSPLTokenSwap::new(
                     &accounts.token_swap_program,
-                    &accounts.common.token_program,
+                    &accounts.common.token_a_program,
                     &accounts.swap,
                     &accounts.swap_authority,
                     &accounts.common.vault.to_account_info(),
@@ -107,79 +336,615 @@ impl<'a, 'info> Executable for Drip<'a, 'info> {
                     &accounts.swap_token_mint,
                     &accounts.swap_fee_account,
                     swap_amount,
-                    1,
+                    minimum_out,
                 );
 
-                execute_drip(&mut accounts.common, &swap, cpi_executor)
+                execute_drip(
+                    &mut accounts.common,
+                    &mut [],
+                    &[&swap],
+                    DripSwap {
+                        swap_program: accounts.token_swap_program.key(),
+                        swap_account: accounts.swap.key(),
+                        minimum_amount_out: minimum_out,
+                        oracle: accounts.price_oracle.as_ref(),
+                    },
+                    cpi_executor,
+                )
             }
             Drip::OrcaWhirlpool { accounts } => {
-                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common);
-                let sqrt_price_limit = calculate_sqrt_price_limit(
-                    accounts.whirlpool.sqrt_price,
-                    accounts.common.vault.max_slippage_bps,
+                execute_drip_orca_whirlpool(accounts, &mut [], cpi_executor)
+            }
+            Drip::OrcaWhirlpoolCatchUp {
+                accounts,
+                remaining_accounts,
+            } => {
+                let mut catch_up_vault_periods = load_catch_up_vault_periods(remaining_accounts)?;
+                execute_drip_orca_whirlpool(accounts, &mut catch_up_vault_periods, cpi_executor)?;
+                // Remaining accounts are not written back by anchor
+                for vault_period in catch_up_vault_periods.iter() {
+                    vault_period.exit(&crate::ID)?;
+                }
+                Ok(())
+            }
+            Drip::OrcaWhirlpoolTwoHop { accounts } => {
+                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common, &[]);
+                let max_slippage_bps = accounts.common.vault.max_slippage_bps;
+                let leg_one_sqrt_price_limit = calculate_sqrt_price_limit(
+                    accounts.whirlpool_one.sqrt_price,
+                    max_slippage_bps,
                     accounts.common.vault_token_a_account.mint.key()
                         == accounts.common.swap_token_a_account.mint.key(),
                 );
+                let leg_two_sqrt_price_limit = calculate_sqrt_price_limit(
+                    accounts.whirlpool_two.sqrt_price,
+                    max_slippage_bps,
+                    accounts.intermediate_token_account.mint.key()
+                        == accounts.whirlpool_two_token_vault_a.mint.key(),
+                );
 
-                let swap = SwapOrcaWhirlpool::new(
+                let leg_one = SwapOrcaWhirlpool::new(
                     &accounts.whirlpool_program,
-                    &accounts.common.token_program,
+                    &accounts.common.token_a_program,
                     &accounts.common.vault.to_account_info(),
-                    &accounts.whirlpool.to_account_info(),
+                    &accounts.whirlpool_one.to_account_info(),
                     &accounts.common.vault_token_a_account,
                     &accounts.common.swap_token_a_account,
+                    &accounts.intermediate_token_account,
+                    &accounts.common.swap_token_b_account,
+                    &accounts.tick_array_one_0,
+                    &accounts.tick_array_one_1,
+                    &accounts.tick_array_one_2,
+                    &accounts.oracle_one,
+                    swap_amount,
+                    leg_one_sqrt_price_limit,
+                );
+                // amount_in is set to the intermediate balance once the first leg has executed
+                let leg_two = SwapOrcaWhirlpool::new(
+                    &accounts.whirlpool_program,
+                    &accounts.common.token_a_program,
+                    &accounts.common.vault.to_account_info(),
+                    &accounts.whirlpool_two.to_account_info(),
+                    &accounts.intermediate_token_account,
+                    &accounts.whirlpool_two_token_vault_a,
                     &accounts.common.vault_token_b_account,
+                    &accounts.whirlpool_two_token_vault_b,
+                    &accounts.tick_array_two_0,
+                    &accounts.tick_array_two_1,
+                    &accounts.tick_array_two_2,
+                    &accounts.oracle_two,
+                    0,
+                    leg_two_sqrt_price_limit,
+                );
+                let swap = SwapTwoHop::new(leg_one, leg_two, &accounts.intermediate_token_account);
+                // Both legs at their spot prices, the slippage is only tolerated end to end
+                let expected_intermediate_amount = calculate_whirlpool_minimum_out(
+                    swap_amount,
+                    accounts.whirlpool_one.sqrt_price,
+                    accounts.whirlpool_one.fee_rate,
+                    accounts.common.vault_token_a_account.mint.key()
+                        == accounts.common.swap_token_a_account.mint.key(),
+                    0,
+                )?;
+                let minimum_out = calculate_whirlpool_minimum_out(
+                    expected_intermediate_amount,
+                    accounts.whirlpool_two.sqrt_price,
+                    accounts.whirlpool_two.fee_rate,
+                    accounts.intermediate_token_account.mint.key()
+                        == accounts.whirlpool_two_token_vault_a.mint.key(),
+                    max_slippage_bps,
+                )?;
+
+                execute_drip(
+                    &mut accounts.common,
+                    &mut [],
+                    &[&swap],
+                    DripSwap {
+                        swap_program: accounts.whirlpool_program.key(),
+                        swap_account: accounts.whirlpool_one.key(),
+                        minimum_amount_out: minimum_out,
+                        oracle: accounts.price_oracle.as_ref(),
+                    },
+                    cpi_executor,
+                )
+            }
+            Drip::Raydium { accounts } => {
+                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common, &[]);
+                let (reserve_a, reserve_b) = get_raydium_reserves(
+                    &accounts.amm,
+                    &accounts.amm_open_orders,
+                    &accounts.common.swap_token_a_account,
                     &accounts.common.swap_token_b_account,
-                    &accounts.tick_array_0,
-                    &accounts.tick_array_1,
-                    &accounts.tick_array_2,
-                    &accounts.oracle,
+                    &accounts.serum_coin_vault_account,
+                )?;
+                let minimum_out = calculate_constant_product_minimum_out(
+                    get_raydium_amount_in_after_fees(&accounts.amm, swap_amount)?,
+                    reserve_a,
+                    reserve_b,
+                    accounts.common.vault.max_slippage_bps,
+                )?;
+                let swap = SwapRaydium::new(
+                    SwapRaydiumAccounts {
+                        amm_program: &accounts.amm_program,
+                        token_program: &accounts.common.token_a_program,
+                        amm: &accounts.amm,
+                        amm_authority: &accounts.amm_authority,
+                        amm_open_orders: &accounts.amm_open_orders,
+                        amm_target_orders: &accounts.amm_target_orders,
+                        swap_token_a_account: &accounts.common.swap_token_a_account,
+                        swap_token_b_account: &accounts.common.swap_token_b_account,
+                        serum_program: &accounts.serum_program,
+                        serum_market: &accounts.serum_market,
+                        serum_bids: &accounts.serum_bids,
+                        serum_asks: &accounts.serum_asks,
+                        serum_event_queue: &accounts.serum_event_queue,
+                        serum_coin_vault_account: &accounts.serum_coin_vault_account,
+                        serum_pc_vault_account: &accounts.serum_pc_vault_account,
+                        serum_vault_signer: &accounts.serum_vault_signer,
+                        user_source_token_account: &accounts.common.vault_token_a_account,
+                        user_destination_token_account: &accounts.common.vault_token_b_account,
+                        user_owner: &accounts.common.vault.to_account_info(),
+                    },
                     swap_amount,
-                    sqrt_price_limit,
+                    minimum_out,
                 );
 
-                execute_drip(&mut accounts.common, &swap, cpi_executor)
+                execute_drip(
+                    &mut accounts.common,
+                    &mut [],
+                    &[&swap],
+                    DripSwap {
+                        swap_program: accounts.amm_program.key(),
+                        swap_account: accounts.amm.key(),
+                        minimum_amount_out: minimum_out,
+                        oracle: accounts.price_oracle.as_ref(),
+                    },
+                    cpi_executor,
+                )
+            }
+            Drip::Split {
+                accounts,
+                remaining_accounts,
+                params,
+            } => {
+                let leg_accounts = get_split_leg_accounts(&params.legs, remaining_accounts)?;
+                let mut swaps: Vec<Box<dyn CPI + 'info>> = Vec::with_capacity(params.legs.len());
+                // The aggregate token b received must cover every leg's minimum out
+                let mut minimum_out: u64 = 0;
+                for (leg, leg_accounts) in params.legs.iter().zip(leg_accounts.iter()) {
+                    let (swap, leg_minimum_out): (Box<dyn CPI + 'info>, u64) = match leg.venue {
+                        SwapVenue::SPLTokenSwap => {
+                            let (swap, leg_minimum_out) = new_split_spl_token_swap(
+                                &accounts.common,
+                                leg_accounts,
+                                leg.amount_in,
+                            )?;
+                            (Box::new(swap), leg_minimum_out)
+                        }
+                        SwapVenue::OrcaWhirlpool => {
+                            let (swap, leg_minimum_out) = new_split_orca_whirlpool(
+                                &accounts.common,
+                                leg_accounts,
+                                leg.amount_in,
+                            )?;
+                            (Box::new(swap), leg_minimum_out)
+                        }
+                    };
+                    swaps.push(swap);
+                    minimum_out = minimum_out
+                        .checked_add(leg_minimum_out)
+                        .ok_or(InvalidMinimumOut)?;
+                }
+                let swaps: Vec<&dyn CPI> = swaps.iter().map(|swap| swap.as_ref()).collect();
+
+                // The drip event reports the venue of the first leg
+                execute_drip(
+                    &mut accounts.common,
+                    &mut [],
+                    &swaps,
+                    DripSwap {
+                        swap_program: *leg_accounts[0][0].key,
+                        swap_account: *leg_accounts[0][1].key,
+                        minimum_amount_out: minimum_out,
+                        oracle: accounts.price_oracle.as_ref(),
+                    },
+                    cpi_executor,
+                )
+            }
+            Drip::Aggregator {
+                accounts,
+                remaining_accounts,
+                params,
+            } => {
+                let (swap_amount, _) = get_token_a_swap_and_spread_amount(&accounts.common, &[]);
+                // The route is caller supplied, so the output is bounded by the oracle price
+                // less the vault's max slippage
+                let oracle_price_x64 = load_oracle_price_x64(
+                    &accounts.common.vault,
+                    accounts.price_oracle.as_ref().unwrap(),
+                )?;
+                let minimum_amount_out = params.minimum_amount_out.max(calculate_minimum_out(
+                    swap_amount,
+                    oracle_price_x64,
+                    accounts.common.vault.max_slippage_bps,
+                )?);
+
+                let swap = SwapAggregator::new(
+                    &accounts.router_program,
+                    &accounts.common.vault.to_account_info(),
+                    remaining_accounts,
+                    &params.swap_data,
+                );
+
+                execute_drip(
+                    &mut accounts.common,
+                    &mut [],
+                    &[&swap],
+                    DripSwap {
+                        swap_program: accounts.router_program.key(),
+                        swap_account: accounts.router_program.key(),
+                        minimum_amount_out,
+                        oracle: accounts.price_oracle.as_ref(),
+                    },
+                    cpi_executor,
+                )
             }
         }
     }
 }
 
-fn get_token_a_swap_and_spread_amount(accounts: &DripCommonAccounts) -> (u64, u64) {
-    let drip_trigger_spread_amount = calculate_spread_amount(
-        accounts.vault.drip_amount,
-        accounts.vault_proto_config.token_a_drip_trigger_spread,
+fn get_token_a_swap_and_spread_amount(
+    accounts: &DripCommonAccounts,
+    catch_up_vault_periods: &[Account<VaultPeriod>],
+) -> (u64, u64) {
+    let drip_amount = get_drip_amount(accounts, catch_up_vault_periods);
+    let token_a_drip_trigger_spread = accounts.vault_proto_config.get_token_a_swap_spread();
+    let drip_trigger_spread_amount =
+        calculate_spread_amount(drip_amount, token_a_drip_trigger_spread);
+
+    let swap_amount = drip_amount.checked_sub(drip_trigger_spread_amount).unwrap();
+
+    (swap_amount, drip_trigger_spread_amount)
+}
+
+// Token a dripped over the current period and the catch up periods after it,
+// each period drips what is left of the previous period's drip amount after its dar
+fn get_drip_amount(
+    accounts: &DripCommonAccounts,
+    catch_up_vault_periods: &[Account<VaultPeriod>],
+) -> u64 {
+    let mut period_drip_amount = accounts.vault.drip_amount;
+    let mut drip_amount = period_drip_amount;
+    let mut previous_dar = accounts.current_vault_period.dar;
+    for vault_period in catch_up_vault_periods {
+        period_drip_amount = period_drip_amount.checked_sub(previous_dar).unwrap();
+        drip_amount = drip_amount.checked_add(period_drip_amount).unwrap();
+        previous_dar = vault_period.dar;
+    }
+    drip_amount
+}
+
+fn load_catch_up_vault_periods<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, VaultPeriod>>> {
+    remaining_accounts
+        .iter()
+        .map(|account| {
+            validate!(account.is_writable, InvalidCatchUpDrip);
+            Account::try_from(account)
+        })
+        .collect()
+}
+
+fn validate_catch_up(
+    accounts: &DripCommonAccounts,
+    catch_up_vault_periods: &[Account<VaultPeriod>],
+) -> Result<()> {
+    let vault = &accounts.vault;
+    validate!(
+        vault.missed_drip_policy == MissedDripPolicy::CatchUp
+            && !catch_up_vault_periods.is_empty()
+            && catch_up_vault_periods.len() <= MAX_CATCH_UP_PERIODS,
+        InvalidCatchUpDrip
     );
 
-    let swap_amount = accounts
-        .vault
-        .drip_amount
-        .checked_sub(drip_trigger_spread_amount)
+    for (i, vault_period) in catch_up_vault_periods.iter().enumerate() {
+        validate!(vault_period.vault == vault.key(), InvalidVaultReference);
+        validate!(
+            vault_period.period_id
+                == accounts
+                    .current_vault_period
+                    .period_id
+                    .checked_add(i as u64 + 1)
+                    .unwrap(),
+            InvalidVaultPeriod
+        );
+    }
+
+    // The last caught up period must have been activated as well
+    let last_drip_activation_timestamp = vault
+        .drip_activation_timestamp
+        .checked_add(
+            (accounts.vault_proto_config.granularity as i64)
+                .checked_mul(catch_up_vault_periods.len() as i64)
+                .unwrap(),
+        )
         .unwrap();
+    validate!(
+        Clock::get()?.unix_timestamp >= last_drip_activation_timestamp,
+        DuplicateDripError
+    );
 
-    (swap_amount, drip_trigger_spread_amount)
+    Ok(())
+}
+
+fn get_split_leg_accounts<'info>(
+    legs: &[DripSplitLeg],
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<&'info [AccountInfo<'info>]>> {
+    let mut leg_accounts = Vec::with_capacity(legs.len());
+    let mut remaining_accounts = remaining_accounts;
+    for leg in legs {
+        let account_count = leg.venue.leg_account_count();
+        validate!(remaining_accounts.len() >= account_count, InvalidDripSplit);
+        let (accounts, rest) = remaining_accounts.split_at(account_count);
+        leg_accounts.push(accounts);
+        remaining_accounts = rest;
+    }
+    validate!(remaining_accounts.is_empty(), InvalidDripSplit);
+
+    Ok(leg_accounts)
+}
+
+fn new_split_spl_token_swap<'info>(
+    common: &DripCommonAccounts<'info>,
+    accounts: &'info [AccountInfo<'info>],
+    amount_in: u64,
+) -> Result<(SwapSPLTokenSwap<'info>, u64)> {
+    let token_swap_program = Program::try_from(&accounts[0])?;
+    let swap_token_a_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+    let swap_token_b_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[4])?);
+    let swap_token_mint = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[5])?);
+    let swap_fee_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[6])?);
+    let minimum_out = get_spl_token_swap_minimum_out(
+        &accounts[1],
+        &swap_token_a_account,
+        &swap_token_b_account,
+        amount_in,
+        common.vault.max_slippage_bps,
+    )?;
+
+    let swap = SwapSPLTokenSwap::new(
+        &token_swap_program,
+        &common.token_a_program,
+        &accounts[1],
+        &accounts[2],
+        &common.vault.to_account_info(),
+        &common.vault_token_a_account,
+        &swap_token_a_account,
+        &swap_token_b_account,
+        &common.vault_token_b_account,
+        &swap_token_mint,
+        &swap_fee_account,
+        amount_in,
+        minimum_out,
+    );
+
+    Ok((swap, minimum_out))
+}
+
+fn new_split_orca_whirlpool<'info>(
+    common: &DripCommonAccounts<'info>,
+    accounts: &'info [AccountInfo<'info>],
+    amount_in: u64,
+) -> Result<(SwapOrcaWhirlpool<'info>, u64)> {
+    let whirlpool_program = Program::try_from(&accounts[0])?;
+    let whirlpool = Account::<Whirlpool>::try_from(&accounts[1])?;
+    let token_vault_a = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?);
+    let token_vault_b = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+    let oracle = UncheckedAccount::try_from(&accounts[7]);
+    let a_to_b = common.vault_token_a_account.mint.key() == token_vault_a.mint.key();
+    let sqrt_price_limit =
+        calculate_sqrt_price_limit(whirlpool.sqrt_price, common.vault.max_slippage_bps, a_to_b);
+    let minimum_out = calculate_whirlpool_minimum_out(
+        amount_in,
+        whirlpool.sqrt_price,
+        whirlpool.fee_rate,
+        a_to_b,
+        common.vault.max_slippage_bps,
+    )?;
+
+    let swap = SwapOrcaWhirlpool::new(
+        &whirlpool_program,
+        &common.token_a_program,
+        &common.vault.to_account_info(),
+        &whirlpool.to_account_info(),
+        &common.vault_token_a_account,
+        &token_vault_a,
+        &common.vault_token_b_account,
+        &token_vault_b,
+        &accounts[4],
+        &accounts[5],
+        &accounts[6],
+        &oracle,
+        amount_in,
+        sqrt_price_limit,
+    );
+
+    Ok((swap, minimum_out))
+}
+
+// Output at the pool's spot price (after curve fees) less the vault's max slippage
+fn get_spl_token_swap_minimum_out(
+    swap: &AccountInfo,
+    swap_token_a_account: &TokenAccount,
+    swap_token_b_account: &TokenAccount,
+    swap_amount: u64,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let token_swap = SwapVersion::unpack(&swap.data.borrow())?;
+
+    // The spot price is only derived from the reserves for constant product pools
+    validate!(
+        token_swap.swap_curve().curve_type == CurveType::ConstantProduct,
+        UnsupportedCurveType
+    );
+
+    let fees = token_swap.fees();
+    let amount = u128::from(swap_amount);
+    let amount_in = fees
+        .trading_fee(amount)
+        .zip(fees.owner_trading_fee(amount))
+        .and_then(|(trading_fee, owner_trading_fee)| {
+            amount.checked_sub(trading_fee.checked_add(owner_trading_fee)?)
+        })
+        .and_then(|amount_in| u64::try_from(amount_in).ok())
+        .ok_or(InvalidMinimumOut)?;
+
+    calculate_constant_product_minimum_out(
+        amount_in,
+        swap_token_a_account.amount,
+        swap_token_b_account.amount,
+        max_slippage_bps,
+    )
+}
+
+// Price of a base unit of token a in base units of token b, rejecting stale oracle prices
+fn load_oracle_price_x64(vault: &Vault, oracle: &AccountInfo) -> Result<u128> {
+    let oracle_price = OraclePrice::load(oracle)?;
+    validate!(
+        !oracle_price.is_stale(Clock::get()?.unix_timestamp),
+        StaleOraclePrice
+    );
+
+    Ok(calculate_oracle_price_x64(
+        u64::try_from(oracle_price.price).map_err(|_| InvalidOraclePrice)?,
+        oracle_price.expo,
+        vault.token_a_decimals,
+        vault.token_b_decimals,
+    )
+    .ok_or(InvalidOraclePrice)?)
+}
+
+fn validate_price_against_oracle(
+    vault: &Vault,
+    oracle: &AccountInfo,
+    sent_a: u64,
+    received_b: u64,
+) -> Result<()> {
+    let oracle_price_x64 = load_oracle_price_x64(vault, oracle)?;
+    validate!(
+        is_price_within_deviation(
+            compute_price(received_b, sent_a),
+            oracle_price_x64,
+            vault.max_oracle_deviation_bps,
+        ),
+        OraclePriceDeviationExceeded
+    );
+
+    Ok(())
+}
+
+// Pays the lamport bounty out of the vault's lamports above rent exemption,
+// the keeper gets whatever is left if the reserve can't cover the full bounty
+fn pay_keeper_reward_lamports(accounts: &DripCommonAccounts) -> Result<u64> {
+    let vault_info = accounts.vault.to_account_info();
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(vault_info.data_len());
+    let reward_lamports = vault_info
+        .lamports()
+        .saturating_sub(rent_exempt_lamports)
+        .min(accounts.vault_proto_config.keeper_reward_lamports);
+
+    if reward_lamports > 0 {
+        **vault_info.try_borrow_mut_lamports()? -= reward_lamports;
+        **accounts
+            .drip_trigger_source
+            .to_account_info()
+            .try_borrow_mut_lamports()? += reward_lamports;
+    }
+
+    Ok(reward_lamports)
+}
+
+fn execute_drip_orca_whirlpool<'info>(
+    accounts: &mut DripOrcaWhirlpoolAccounts<'info>,
+    catch_up_vault_periods: &mut [Account<'info, VaultPeriod>],
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    let (swap_amount, _) =
+        get_token_a_swap_and_spread_amount(&accounts.common, catch_up_vault_periods);
+    let sqrt_price_limit = calculate_sqrt_price_limit(
+        accounts.whirlpool.sqrt_price,
+        accounts.common.vault.max_slippage_bps,
+        accounts.common.vault_token_a_account.mint.key()
+            == accounts.common.swap_token_a_account.mint.key(),
+    );
+
+    let swap = SwapOrcaWhirlpool::new(
+        &accounts.whirlpool_program,
+        &accounts.common.token_a_program,
+        &accounts.common.vault.to_account_info(),
+        &accounts.whirlpool.to_account_info(),
+        &accounts.common.vault_token_a_account,
+        &accounts.common.swap_token_a_account,
+        &accounts.common.vault_token_b_account,
+        &accounts.common.swap_token_b_account,
+        &accounts.tick_array_0,
+        &accounts.tick_array_1,
+        &accounts.tick_array_2,
+        &accounts.oracle,
+        swap_amount,
+        sqrt_price_limit,
+    );
+
+    execute_drip(
+        &mut accounts.common,
+        catch_up_vault_periods,
+        &[&swap],
+        DripSwap {
+            swap_program: accounts.whirlpool_program.key(),
+            swap_account: accounts.whirlpool.key(),
+            minimum_amount_out: 1,
+            oracle: accounts.price_oracle.as_ref(),
+        },
+        cpi_executor,
+    )
+}
+
+// The swap being dripped through, as reported in the drip event and checked after the swap
+struct DripSwap<'a, 'info> {
+    swap_program: Pubkey,
+    swap_account: Pubkey,
+    minimum_amount_out: u64,
+    oracle: Option<&'a UncheckedAccount<'info>>,
 }
 
 #[inline(never)]
-fn execute_drip(
-    accounts: &mut DripCommonAccounts,
-    swap: &dyn CPI,
+fn execute_drip<'info>(
+    accounts: &mut DripCommonAccounts<'info>,
+    catch_up_vault_periods: &mut [Account<'info, VaultPeriod>],
+    swaps: &[&dyn CPI],
+    drip_swap: DripSwap,
     cpi_executor: &mut dyn CpiExecutor,
 ) -> Result<()> {
-    let current_drip_amount = accounts.vault.drip_amount;
-    msg!("drip_amount {:?}", current_drip_amount);
-
+    let DripSwap {
+        swap_program,
+        swap_account,
+        minimum_amount_out,
+        oracle,
+    } = drip_swap;
+    let current_drip_amount = get_drip_amount(accounts, catch_up_vault_periods);
     let current_balance_a = accounts.vault_token_a_account.amount;
-    msg!("current_balance_a {:?}", current_balance_a);
-
     let current_balance_b = accounts.vault_token_b_account.amount;
-    msg!("current_balance_b {:?}", current_balance_b);
 
-    let (swap_amount, drip_trigger_spread_amount) = get_token_a_swap_and_spread_amount(accounts);
+    let (swap_amount, drip_trigger_spread_amount) =
+        get_token_a_swap_and_spread_amount(accounts, catch_up_vault_periods);
 
     let drip_trigger_fee_transfer = TransferToken::new(
-        &accounts.token_program,
+        &accounts.token_a_program,
+        &accounts.token_a_mint,
         &accounts.vault_token_a_account,
-        &accounts.drip_fee_token_a_account,
+        &accounts.drip_fee_token_account.to_account_info(),
         &accounts.vault.to_account_info(),
         drip_trigger_spread_amount,
     );
@@ -189,20 +954,31 @@ fn execute_drip(
         &accounts.current_vault_period,
         accounts.vault_proto_config.granularity,
     );
+    for vault_period in catch_up_vault_periods.iter() {
+        accounts
+            .vault
+            .process_drip(vault_period, accounts.vault_proto_config.granularity);
+    }
 
     /* MANUAL CPI (INTERACTIONS) */
     let signer: &Vault = &accounts.vault;
+    let keeper_reward_type = accounts.vault_proto_config.keeper_reward_type;
 
-    cpi_executor.execute_all(vec![&Some(&drip_trigger_fee_transfer), &Some(swap)], signer)?;
+    let drip_trigger_fee_transfer: Option<&dyn CPI> = match keeper_reward_type {
+        KeeperRewardType::TokenA => Some(&drip_trigger_fee_transfer),
+        KeeperRewardType::TokenB | KeeperRewardType::Lamports => None,
+    };
+    let swaps: Vec<Option<&dyn CPI>> = swaps.iter().map(|&swap| Some(swap)).collect();
+    let mut cpis = vec![&drip_trigger_fee_transfer];
+    cpis.extend(swaps.iter());
+    cpi_executor.execute_all(cpis, signer)?;
 
     /* POST CPI VERIFICATION */
     accounts.vault_token_a_account.reload()?;
     accounts.vault_token_b_account.reload()?;
 
     let new_balance_a = accounts.vault_token_a_account.amount;
-    msg!("new_balance_a {:?}", new_balance_a);
     let new_balance_b = accounts.vault_token_b_account.amount;
-    msg!("new_balance_b {:?}", new_balance_b);
     let received_b = new_balance_b.checked_sub(current_balance_b).unwrap();
     let used_a = current_balance_a.checked_sub(new_balance_a).unwrap();
 
@@ -211,24 +987,132 @@ fn execute_drip(
         return Err(DripError::IncompleteSwapError.into());
     }
 
+    // Transfer fees are withheld from the receiving account, so they never change used_a
     if used_a != (swap_amount + drip_trigger_spread_amount) || used_a != current_drip_amount {
         return Err(DripError::IncorrectSwapAmount.into());
     }
 
+    // received_b is net of the token b transfer fee, the swap is judged on what it paid out
+    let swapped_b = received_b
+        .checked_add(calculate_inverse_transfer_fee(
+            &accounts.token_b_mint.to_account_info(),
+            received_b,
+        )?)
+        .unwrap();
+
+    if swapped_b < minimum_amount_out {
+        return Err(DripError::InsufficientSwapOutput.into());
+    }
+
+    if let Some(oracle) = oracle.filter(|_| accounts.vault.has_oracle()) {
+        validate_price_against_oracle(&accounts.vault, oracle, swap_amount, swapped_b)?;
+    }
+
+    // The vault signs for the swap, make sure it still fully controls its token accounts
+    let vault_key = accounts.vault.key();
+    for token_account in [
+        &accounts.vault_token_a_account,
+        &accounts.vault_token_b_account,
+    ] {
+        validate!(
+            token_account.owner == vault_key
+                && token_account.delegate.is_none()
+                && token_account.close_authority.is_none(),
+            VaultTokenAccountModified
+        );
+    }
+
+    /* KEEPER REWARD (INTERACTIONS) */
+    let mut drip_trigger_spread_b = 0;
+    let mut drip_trigger_reward_lamports = 0;
+    match keeper_reward_type {
+        KeeperRewardType::TokenA => {}
+        KeeperRewardType::TokenB => {
+            let (token_a_drip_trigger_spread, _, _) = accounts.vault_proto_config.get_spreads();
+            drip_trigger_spread_b =
+                calculate_spread_amount(received_b, token_a_drip_trigger_spread);
+            if drip_trigger_spread_b > 0 {
+                let drip_trigger_fee_transfer = TransferToken::new(
+                    &accounts.token_b_program,
+                    &accounts.token_b_mint,
+                    &accounts.vault_token_b_account,
+                    &accounts.drip_fee_token_account.to_account_info(),
+                    &accounts.vault.to_account_info(),
+                    drip_trigger_spread_b,
+                );
+                let signer: &Vault = &accounts.vault;
+                let drip_trigger_fee_transfer: Option<&dyn CPI> = Some(&drip_trigger_fee_transfer);
+                cpi_executor.execute_all(vec![&drip_trigger_fee_transfer], signer)?;
+            }
+        }
+        KeeperRewardType::Lamports => {
+            drip_trigger_reward_lamports = pay_keeper_reward_lamports(accounts)?;
+        }
+    }
+
     /* POST CPI STATE UPDATES (EFFECTS) */
-    accounts
-        .current_vault_period
-        .update_twap(&accounts.last_vault_period, swap_amount, received_b);
+    // The keeper's token b is not owed to positions, so it is kept out of the twap
+    accounts.current_vault_period.update_twap(
+        &accounts.last_vault_period,
+        swap_amount,
+        received_b.checked_sub(drip_trigger_spread_b).unwrap(),
+        accounts.vault_proto_config.granularity,
+    );
 
+    accounts.current_vault_period.update_b_per_a_cumulative(
+        &accounts.last_vault_period,
+        current_drip_amount,
+        received_b.checked_sub(drip_trigger_spread_b).unwrap(),
+    );
     accounts.current_vault_period.update_drip_timestamp();
 
+    // Every caught up period is priced at the price of the combined swap
+    for i in 0..catch_up_vault_periods.len() {
+        let (previous_vault_periods, vault_periods) = catch_up_vault_periods.split_at_mut(i);
+        let previous_vault_period = previous_vault_periods
+            .last()
+            .unwrap_or(accounts.current_vault_period.as_ref());
+        vault_periods[0].update_twap(
+            previous_vault_period,
+            swap_amount,
+            received_b.checked_sub(drip_trigger_spread_b).unwrap(),
+            accounts.vault_proto_config.granularity,
+        );
+        vault_periods[0].update_b_per_a_cumulative(
+            previous_vault_period,
+            current_drip_amount,
+            received_b.checked_sub(drip_trigger_spread_b).unwrap(),
+        );
+        vault_periods[0].update_drip_timestamp();
+    }
+    let last_vault_period = catch_up_vault_periods
+        .last()
+        .unwrap_or(accounts.current_vault_period.as_ref());
+
+    emit!(DripEvent {
+        vault: accounts.vault.key(),
+        first_period_id: accounts.current_vault_period.period_id,
+        period_id: last_vault_period.period_id,
+        drip_trigger_source: accounts.drip_trigger_source.key(),
+        swap_program,
+        swap: swap_account,
+        sent_a: swap_amount,
+        received_b,
+        drip_trigger_spread_a: drip_trigger_spread_amount,
+        drip_trigger_spread_b,
+        drip_trigger_reward_lamports,
+        twap: last_vault_period.twap,
+        drip_timestamp: last_vault_period.drip_timestamp,
+    });
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use anchor_lang::solana_program::program_pack::Pack;
-    use anchor_spl::token::{Token, TokenAccount};
+    use anchor_spl::token_2022::spl_token_2022;
+    use anchor_spl::token_interface::TokenAccount;
 
     use super::*;
     use crate::{
@@ -245,10 +1129,10 @@ mod tests {
 
     impl<'info> TestDripCpiExecutor<'info> {
         pub fn set_token_account_balance(
-            account: &mut Account<'info, TokenAccount>,
+            account: &mut InterfaceAccount<'info, TokenAccount>,
             amount: u64,
         ) -> Result<()> {
-            let token_account = spl_token::state::Account {
+            let token_account = spl_token_2022::state::Account {
                 mint: account.mint,
                 owner: account.owner,
                 amount,
@@ -260,13 +1144,13 @@ mod tests {
             };
 
             let mut buff: Vec<u8> = vec![];
-            spl_token::state::Account::pack(token_account, &mut buff)?;
+            spl_token_2022::state::Account::pack(token_account, &mut buff)?;
 
             let token_account = TokenAccount::try_deserialize(&mut buff.as_slice())?;
 
             account.set_inner(token_account);
 
-            account.exit(&Token::id())
+            account.exit(&anchor_spl::token::ID)
         }
     }
 
@@ -1,16 +1,27 @@
-use crate::errors::DripError::{InvalidGranularity, InvalidSpread};
+use crate::errors::DripError;
+use crate::events::{VaultPeriodInitialized, VaultProtoConfigInitialized};
 use crate::instruction_accounts::InitializeVaultPeriodAccountsBumps;
 use crate::interactions::executor::CpiExecutor;
-use crate::state::MAX_TOKEN_SPREAD_EXCLUSIVE;
+use crate::interactions::transfer_lamports::TransferLamports;
+use crate::validate;
 use crate::{
     instruction_accounts::{
         InitializeVaultPeriodAccounts, InitializeVaultPeriodParams,
         InitializeVaultProtoConfigAccounts, InitializeVaultProtoConfigParams,
+        ReallocVaultPeriodAccounts,
     },
     state::traits::{Executable, Validatable},
-    validate,
+    state::{KeeperReward, VaultPeriod, VaultProtoConfig},
 };
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::PUBKEY_BYTES;
+use anchor_lang::Discriminator;
+
+// Vault periods being reallocated predate the latest fields and can't be deserialized,
+// so only the discriminator and the leading vault field are read from the raw data
+const DISCRIMINATOR_LENGTH: usize = 8;
+const VAULT_PERIOD_VAULT_OFFSET: usize = DISCRIMINATOR_LENGTH;
+const VAULT_PERIOD_VAULT_END: usize = VAULT_PERIOD_VAULT_OFFSET + PUBKEY_BYTES;
 
 pub enum Init<'a, 'info> {
     VaultProtoConfig {
@@ -22,28 +33,52 @@ pub enum Init<'a, 'info> {
         params: InitializeVaultPeriodParams,
         bumps: InitializeVaultPeriodAccountsBumps,
     },
+    ReallocVaultPeriod {
+        accounts: &'a mut ReallocVaultPeriodAccounts<'info>,
+    },
 }
 
 impl<'a, 'info> Validatable for Init<'a, 'info> {
     fn validate(&self) -> Result<()> {
         match self {
             Init::VaultProtoConfig { params, .. } => {
-                validate!(params.granularity > 0, InvalidGranularity);
+                VaultProtoConfig::validate_params(
+                    params.granularity,
+                    params.token_a_drip_trigger_spread,
+                    params.token_b_withdrawal_spread,
+                    params.token_b_referral_spread,
+                    params.calendar_aligned,
+                )?;
+                VaultProtoConfig::validate_keeper_reward(
+                    params.keeper_reward_type,
+                    params.keeper_reward_lamports,
+                )
+            }
+            Init::VaultPeriod { .. } => Ok(()),
+            Init::ReallocVaultPeriod { accounts } => {
+                let data = accounts.vault_period.try_borrow_data()?;
+                validate!(
+                    data.len() >= VAULT_PERIOD_VAULT_END
+                        && data[..DISCRIMINATOR_LENGTH] == VaultPeriod::DISCRIMINATOR,
+                    DripError::InvalidVaultPeriod
+                );
+                validate!(
+                    data[VAULT_PERIOD_VAULT_OFFSET..VAULT_PERIOD_VAULT_END]
+                        == accounts.vault.key().to_bytes(),
+                    DripError::InvalidVaultReference
+                );
                 validate!(
-                    params.token_a_drip_trigger_spread < MAX_TOKEN_SPREAD_EXCLUSIVE
-                        && params.token_b_withdrawal_spread < MAX_TOKEN_SPREAD_EXCLUSIVE
-                        && params.token_b_referral_spread < MAX_TOKEN_SPREAD_EXCLUSIVE,
-                    InvalidSpread
+                    data.len() < VaultPeriod::ACCOUNT_SPACE + VaultPeriod::RESERVED_SPACE,
+                    DripError::VaultPeriodAlreadyReallocated
                 );
                 Ok(())
             }
-            Init::VaultPeriod { .. } => Ok(()),
         }
     }
 }
 
 impl<'a, 'info> Executable for Init<'a, 'info> {
-    fn execute(self, _cpi_executer: &mut impl CpiExecutor) -> Result<()> {
+    fn execute(self, cpi_executor: &mut impl CpiExecutor) -> Result<()> {
         match self {
             Init::VaultProtoConfig { accounts, params } => {
                 init_vault_proto_config(accounts, params)
@@ -53,6 +88,7 @@ impl<'a, 'info> Executable for Init<'a, 'info> {
                 params,
                 bumps,
             } => init_vault_period(accounts, params, bumps),
+            Init::ReallocVaultPeriod { accounts } => realloc_vault_period(accounts, cpi_executor),
         }
     }
 }
@@ -67,7 +103,23 @@ fn init_vault_proto_config(
         params.token_b_withdrawal_spread,
         params.token_b_referral_spread,
         params.admin,
+        KeeperReward {
+            reward_type: params.keeper_reward_type,
+            lamports: params.keeper_reward_lamports,
+        },
     );
+
+    emit!(VaultProtoConfigInitialized {
+        vault_proto_config: accounts.vault_proto_config.key(),
+        admin: params.admin,
+        granularity: params.granularity,
+        token_a_drip_trigger_spread: params.token_a_drip_trigger_spread,
+        token_b_withdrawal_spread: params.token_b_withdrawal_spread,
+        token_b_referral_spread: params.token_b_referral_spread,
+        keeper_reward_type: params.keeper_reward_type,
+        keeper_reward_lamports: params.keeper_reward_lamports,
+    });
+
     Ok(())
 }
 
@@ -79,13 +131,55 @@ fn init_vault_period(
     accounts
         .vault_period
         .init(accounts.vault.key(), params.period_id, bumps.vault_period);
+
+    emit!(VaultPeriodInitialized {
+        vault: accounts.vault.key(),
+        vault_period: accounts.vault_period.key(),
+        period_id: params.period_id,
+    });
+
+    Ok(())
+}
+
+fn realloc_vault_period(
+    accounts: &mut ReallocVaultPeriodAccounts,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    let vault_period = accounts.vault_period.to_account_info();
+    let rent_top_up = Rent::get()?
+        .minimum_balance(VaultPeriod::ACCOUNT_SPACE + VaultPeriod::RESERVED_SPACE)
+        .saturating_sub(vault_period.lamports());
+
+    /* STATE UPDATES (EFFECTS) */
+    // New fields start zeroed, has_b_per_a_cumulative stays false so positions starting at
+    // this period keep using the twap
+    vault_period.realloc(
+        VaultPeriod::ACCOUNT_SPACE + VaultPeriod::RESERVED_SPACE,
+        true,
+    )?;
+
+    /* MANUAL CPI (INTERACTIONS) */
+    if rent_top_up > 0 {
+        let rent_transfer = TransferLamports::new(
+            &accounts.system_program,
+            &accounts.payer.to_account_info(),
+            &vault_period,
+            rent_top_up,
+        );
+        cpi_executor.execute_all(vec![&Some(&rent_transfer)], &*accounts.vault)?;
+    }
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::DripError::{
+        InvalidGranularity, InvalidKeeperReward, InvalidSpread, InvalidTotalSpread,
+    };
     use crate::interactions::executor::test::TestCpiExecutor;
+    use crate::state::KeeperRewardType;
     use crate::test::fixtures::{
         ADMIN, EMPTY_VAULT_PERIOD, EMPTY_VAULT_PROTO_CONFIG, SYSTEM_PROGRAM, VAULT,
     };
@@ -93,17 +187,23 @@ mod tests {
     use std::convert::TryFrom;
     use test_case::test_case;
 
-    #[test_case(0, 0, 0, 0, Pubkey::new_unique(), Err(InvalidGranularity.into()); "Returns error for invalid granularity")]
-    #[test_case(1, 5001, 0, 0, Pubkey::new_unique(), Err(InvalidSpread.into()); "Returns error for invalid token_a_drip_trigger_spread")]
-    #[test_case(1, 10, 5001, 0, Pubkey::new_unique(), Err(InvalidSpread.into()); "Returns error for invalid token_b_withdrawal_spread")]
-    #[test_case(1, 10, 10, 5001, Pubkey::new_unique(), Err(InvalidSpread.into()); "Returns error for invalid token_b_referral_spread")]
-    #[test_case(1, 10, 10, 10, Pubkey::new_unique(), Ok(()) ; "Returns ok for valid params")]
+    #[test_case(0, 0, 0, 0, Pubkey::new_unique(), false, Err(InvalidGranularity.into()); "Returns error for invalid granularity")]
+    #[test_case(1, 5001, 0, 0, Pubkey::new_unique(), false, Err(InvalidSpread.into()); "Returns error for invalid token_a_drip_trigger_spread")]
+    #[test_case(1, 10, 5001, 0, Pubkey::new_unique(), false, Err(InvalidSpread.into()); "Returns error for invalid token_b_withdrawal_spread")]
+    #[test_case(1, 10, 10, 5001, Pubkey::new_unique(), false, Err(InvalidSpread.into()); "Returns error for invalid token_b_referral_spread")]
+    #[test_case(1, 2000, 2000, 1000, Pubkey::new_unique(), false, Err(InvalidTotalSpread.into()); "Returns error for spreads summing to the cap")]
+    #[test_case(7, 10, 10, 10, Pubkey::new_unique(), true, Err(InvalidGranularity.into()); "Returns error for calendar aligned granularity that doesn't divide a day")]
+    #[test_case(7, 10, 10, 10, Pubkey::new_unique(), false, Ok(()) ; "Returns ok for unaligned granularity when not calendar aligned")]
+    #[test_case(3600, 10, 10, 10, Pubkey::new_unique(), true, Ok(()) ; "Returns ok for calendar aligned granularity")]
+    #[test_case(1, 2000, 2000, 999, Pubkey::new_unique(), false, Ok(()) ; "Returns ok for spreads summing to just under the cap")]
+    #[test_case(1, 10, 10, 10, Pubkey::new_unique(), false, Ok(()) ; "Returns ok for valid params")]
     fn init_vault_proto_config_validate(
         granularity: u64,
         token_a_drip_trigger_spread: u16,
         token_b_withdrawal_spread: u16,
         token_b_referral_spread: u16,
         admin: Pubkey,
+        calendar_aligned: bool,
         expected_res: Result<()>,
     ) {
         let mut signer = ADMIN.clone();
@@ -129,6 +229,9 @@ mod tests {
             token_b_withdrawal_spread,
             token_b_referral_spread,
             admin,
+            calendar_aligned,
+            keeper_reward_type: KeeperRewardType::TokenA,
+            keeper_reward_lamports: 0,
         };
 
         let vault_proto_config_action = Init::VaultProtoConfig {
@@ -165,6 +268,7 @@ mod tests {
         assert_eq!(vault_proto_config_before.token_b_withdrawal_spread, 0);
         assert_eq!(vault_proto_config_before.token_b_referral_spread, 0);
         assert_eq!(vault_proto_config_before.admin, Default::default());
+        assert_eq!(vault_proto_config_before.pending_admin, Default::default());
 
         let admin = Pubkey::new_unique();
 
@@ -174,6 +278,9 @@ mod tests {
             token_b_withdrawal_spread: 3,
             token_b_referral_spread: 4,
             admin,
+            calendar_aligned: false,
+            keeper_reward_type: KeeperRewardType::Lamports,
+            keeper_reward_lamports: 5000,
         };
 
         let vault_proto_config_action = Init::VaultProtoConfig {
@@ -201,6 +308,28 @@ mod tests {
         assert_eq!(vault_proto_config_after.token_b_withdrawal_spread, 3);
         assert_eq!(vault_proto_config_after.token_b_referral_spread, 4);
         assert_eq!(vault_proto_config_after.admin, admin);
+        assert_eq!(vault_proto_config_after.pending_admin, Default::default());
+        assert_eq!(
+            vault_proto_config_after.keeper_reward_type,
+            KeeperRewardType::Lamports
+        );
+        assert_eq!(vault_proto_config_after.keeper_reward_lamports, 5000);
+    }
+
+    #[test_case(KeeperRewardType::TokenA, 0, Ok(()) ; "Returns ok for token a rewards")]
+    #[test_case(KeeperRewardType::TokenB, 0, Ok(()) ; "Returns ok for token b rewards")]
+    #[test_case(KeeperRewardType::Lamports, 5000, Ok(()) ; "Returns ok for a lamport bounty")]
+    #[test_case(KeeperRewardType::Lamports, 0, Err(InvalidKeeperReward.into()); "Returns error for a zero lamport bounty")]
+    #[test_case(KeeperRewardType::TokenB, 5000, Err(InvalidKeeperReward.into()); "Returns error for a lamport bounty outside lamports mode")]
+    fn validate_keeper_reward_tests(
+        keeper_reward_type: KeeperRewardType,
+        keeper_reward_lamports: u64,
+        expected_res: Result<()>,
+    ) {
+        assert_eq!(
+            VaultProtoConfig::validate_keeper_reward(keeper_reward_type, keeper_reward_lamports),
+            expected_res
+        );
     }
 
     #[test]
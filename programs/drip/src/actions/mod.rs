@@ -1,10 +1,12 @@
 pub mod admin;
+pub mod check;
 pub mod deposit;
 pub mod drip;
 pub mod init;
 pub mod withdraw;
 
 pub use admin::*;
+pub use check::*;
 pub use deposit::*;
 pub use drip::*;
 pub use init::*;
@@ -1,17 +1,27 @@
+use crate::actions::deposit::reschedule_drip_amount;
 use crate::interactions::executor::CpiExecutor;
 use crate::state::traits::{Executable, Validatable};
-use crate::{validate, ClosePositionAccounts, WithdrawBAccounts, WithdrawCommonAccounts, CPI};
+use crate::{
+    validate, ClosePositionAccounts, DecreasePositionAccounts, DecreasePositionParams,
+    WithdrawBAccounts, WithdrawCommonAccounts, CPI,
+};
 use std::cmp::min;
 
 use crate::errors::DripError;
+use crate::events::{PositionClosed, PositionDecreased, WithdrawBEvent};
 use crate::interactions::burn_token::BurnToken;
 use crate::interactions::close_account::CloseAccount;
 use crate::interactions::transfer_token::TransferToken;
 use crate::math::{
-    calculate_spread_amount, calculate_withdraw_token_a_amount, calculate_withdraw_token_b_amount,
+    calculate_remaining_swaps, calculate_spread_amount, calculate_withdraw_token_a_amount,
+    calculate_withdraw_token_b_amount, calculate_withdraw_token_b_amount_from_b_per_a,
+};
+use crate::state::{
+    calculate_transfer_fee, Position, Referrer, Vault, VaultPeriod, VaultProtoConfig,
+    WithdrawalSpreadPolicy,
 };
-use crate::state::Vault;
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 pub enum Withdraw<'a, 'info> {
     WithoutClosePosition {
@@ -19,6 +29,12 @@ pub enum Withdraw<'a, 'info> {
     },
     WithClosePosition {
         accounts: &'a mut ClosePositionAccounts<'info>,
+        // Close the user's native token a/b accounts so the withdrawn wSOL arrives as SOL
+        unwrap_sol: bool,
+    },
+    DecreasePosition {
+        accounts: &'a mut DecreasePositionAccounts<'info>,
+        params: DecreasePositionParams,
     },
 }
 
@@ -47,7 +63,10 @@ impl<'a, 'info> Validatable for Withdraw<'a, 'info> {
 
                 Ok(())
             }
-            Withdraw::WithClosePosition { accounts } => {
+            Withdraw::WithClosePosition {
+                accounts,
+                unwrap_sol,
+            } => {
                 validate_common(&accounts.common)?;
 
                 if accounts.common.user_position_nft_account.owner
@@ -103,19 +122,125 @@ impl<'a, 'info> Validatable for Withdraw<'a, 'info> {
                     DripError::IncorrectVaultTokenAccount
                 );
 
+                validate!(
+                    accounts.token_a_mint.key() == accounts.common.vault.token_a_mint,
+                    DripError::InvalidMint
+                );
+
                 validate!(
                     accounts.user_position_nft_mint.key()
                         == accounts.common.user_position.position_authority,
                     DripError::InvalidMint
                 );
 
+                if *unwrap_sol {
+                    // only the nft owner can close their own token accounts
+                    validate!(
+                        accounts.common.user_position_nft_account.owner
+                            == accounts.common.withdrawer.key(),
+                        DripError::InvalidOwner
+                    );
+                    validate!(
+                        is_unwrappable(&accounts.user_token_a_account, &accounts.common.withdrawer)
+                            || is_unwrappable(
+                                &accounts.common.user_token_b_account,
+                                &accounts.common.withdrawer
+                            ),
+                        DripError::InvalidNativeTokenAccount
+                    );
+                }
+
+                Ok(())
+            }
+            Withdraw::DecreasePosition { accounts, params } => {
+                validate_common(&accounts.common)?;
+
+                // only nft owner can decrease their position
+                validate!(
+                    accounts.common.user_position_nft_account.owner
+                        == accounts.common.withdrawer.key(),
+                    DripError::InvalidOwner
+                );
+
+                validate!(
+                    accounts.vault_token_a_account.key() == accounts.common.vault.token_a_account,
+                    DripError::IncorrectVaultTokenAccount
+                );
+
+                validate!(
+                    accounts.token_a_mint.key() == accounts.common.vault.token_a_mint,
+                    DripError::InvalidMint
+                );
+
+                validate!(
+                    accounts.vault_period_user_expiry.vault == accounts.common.vault.key(),
+                    DripError::InvalidVaultReference
+                );
+
+                validate!(
+                    accounts.vault_period_user_expiry.period_id
+                        == accounts
+                            .common
+                            .user_position
+                            .drip_period_id_before_deposit
+                            .checked_add(accounts.common.user_position.number_of_swaps)
+                            .unwrap(),
+                    DripError::InvalidVaultPeriod
+                );
+
+                validate!(params.number_of_swaps > 0, DripError::NumSwapsIsZero);
+
+                validate!(
+                    params.periodic_drip_amount > 0,
+                    DripError::PeriodicDripAmountIsZero
+                );
+
+                let remaining_swaps = get_remaining_swaps(&accounts.common);
+                validate!(
+                    params.number_of_swaps <= remaining_swaps
+                        && params.periodic_drip_amount
+                            <= accounts.common.user_position.periodic_drip_amount,
+                    DripError::InvalidPositionDecrease
+                );
+
+                validate!(
+                    get_decrease_withdrawal_amount_a(&accounts.common, params) > 0,
+                    DripError::WithdrawableAmountIsZero
+                );
+
+                validate!(
+                    accounts.vault_period_new_expiry.vault == accounts.common.vault.key(),
+                    DripError::InvalidVaultReference
+                );
+
+                validate!(
+                    accounts.vault_period_new_expiry.period_id
+                        == accounts
+                            .common
+                            .vault
+                            .last_drip_period
+                            .checked_add(params.number_of_swaps)
+                            .unwrap(),
+                    DripError::InvalidVaultPeriod
+                );
+
                 Ok(())
             }
         }
     }
 }
 
-fn validate_common(accounts: &WithdrawCommonAccounts) -> Result<()> {
+// Native token accounts owned by the withdrawer can be closed to unwrap their wSOL
+fn is_unwrappable(token_account: &InterfaceAccount<TokenAccount>, withdrawer: &Signer) -> bool {
+    token_account.is_native() && token_account.owner == withdrawer.key()
+}
+
+pub(crate) fn validate_common(accounts: &WithdrawCommonAccounts) -> Result<()> {
+    validate!(
+        !accounts.vault.withdrawals_paused,
+        DripError::VaultWithdrawalsArePaused
+    );
+
     // Relation Checks
     validate!(
         accounts.vault_proto_config.key() == accounts.vault.proto_config,
@@ -155,6 +280,11 @@ fn validate_common(accounts: &WithdrawCommonAccounts) -> Result<()> {
         DripError::IncorrectVaultTokenAccount
     );
 
+    validate!(
+        accounts.token_b_mint.key() == accounts.vault.token_b_mint,
+        DripError::InvalidMint
+    );
+
     validate!(
         accounts.vault_treasury_token_b_account.key() == accounts.vault.treasury_token_b_account,
         DripError::IncorrectVaultTokenAccount
@@ -171,10 +301,25 @@ fn validate_common(accounts: &WithdrawCommonAccounts) -> Result<()> {
     );
 
     validate!(
-        accounts.user_position.referrer == accounts.referrer.key(),
+        accounts.referrer.key()
+            == accounts.user_position.get_referrer_token_account(
+                &accounts.vault.token_b_mint,
+                &accounts.token_b_program.key()
+            ),
         DripError::InvalidReferrer
     );
 
+    if let Some(referrer_account) = &accounts.referrer_account {
+        validate!(
+            referrer_account.vault_proto_config == accounts.vault.proto_config,
+            DripError::InvalidVaultProtoConfigReference
+        );
+        validate!(
+            referrer_account.referrer == accounts.user_position.referrer,
+            DripError::InvalidReferrer
+        );
+    }
+
     validate!(
         accounts.user_position_nft_account.mint == accounts.user_position.position_authority,
         DripError::InvalidMint
@@ -192,7 +337,10 @@ impl<'a, 'info> Executable for Withdraw<'a, 'info> {
             Withdraw::WithoutClosePosition { accounts } => {
                 execute_withdraw_b(&mut accounts.common, cpi_executor)
             }
-            Withdraw::WithClosePosition { accounts } => {
+            Withdraw::WithClosePosition {
+                accounts,
+                unwrap_sol,
+            } => {
                 let sol_destination = match &accounts.sol_destination {
                     Some(sol_destination) => sol_destination.to_account_info(),
                     None => accounts.common.withdrawer.to_account_info(),
@@ -205,9 +353,10 @@ impl<'a, 'info> Executable for Withdraw<'a, 'info> {
                 let withdrawable_amount_a = get_withdrawal_amount_a(&accounts.common);
 
                 let transfer_a_to_user = TransferToken::new(
-                    &accounts.common.token_program,
+                    &accounts.token_a_program,
+                    &accounts.token_a_mint,
                     &accounts.vault_token_a_account,
-                    &accounts.user_token_a_account,
+                    &accounts.user_token_a_account.to_account_info(),
                     &accounts.common.vault.to_account_info(),
                     withdrawable_amount_a,
                 );
@@ -250,6 +399,36 @@ impl<'a, 'info> Executable for Withdraw<'a, 'info> {
                         None
                     };
 
+                let unwrap_sol_a = CloseAccount::new(
+                    &accounts.token_a_program,
+                    &accounts.user_token_a_account,
+                    &accounts.common.withdrawer,
+                    &accounts.common.withdrawer,
+                );
+                let unwrap_sol_a: Option<&dyn CPI> = if unwrap_sol
+                    && is_unwrappable(&accounts.user_token_a_account, &accounts.common.withdrawer)
+                {
+                    Some(&unwrap_sol_a)
+                } else {
+                    None
+                };
+
+                let unwrap_sol_b = CloseAccount::new(
+                    &accounts.common.token_b_program,
+                    &accounts.common.user_token_b_account,
+                    &accounts.common.withdrawer,
+                    &accounts.common.withdrawer,
+                );
+                let unwrap_sol_b: Option<&dyn CPI> = if unwrap_sol
+                    && is_unwrappable(
+                        &accounts.common.user_token_b_account,
+                        &accounts.common.withdrawer,
+                    ) {
+                    Some(&unwrap_sol_b)
+                } else {
+                    None
+                };
+
                 /* STATE UPDATES (EFFECTS) */
                 // Update the user's position state to reflect the newly withdrawn amount
                 // Only reduce drip amount and dar if we haven't done so already
@@ -270,10 +449,25 @@ impl<'a, 'info> Executable for Withdraw<'a, 'info> {
                 /* MANUAL CPI (INTERACTIONS) */
                 let signer: &Vault = &accounts.common.vault;
                 cpi_executor.execute_all(
-                    vec![&transfer_a_to_user, &burn_position, &close_account],
+                    vec![
+                        &transfer_a_to_user,
+                        &burn_position,
+                        &close_account,
+                        &unwrap_sol_a,
+                        &unwrap_sol_b,
+                    ],
                     signer,
                 )?;
 
+                emit!(PositionClosed {
+                    vault: accounts.common.vault.key(),
+                    position: accounts.common.user_position.key(),
+                    withdrawer: accounts.common.withdrawer.key(),
+                    withdrawn_a: withdrawable_amount_a,
+                    closed_by_admin: accounts.common.user_position_nft_account.owner
+                        != accounts.common.withdrawer.key(),
+                });
+
                 accounts
                     .common
                     .user_position
@@ -282,11 +476,33 @@ impl<'a, 'info> Executable for Withdraw<'a, 'info> {
 
                 Ok(())
             }
+            Withdraw::DecreasePosition { accounts, params } => {
+                execute_decrease_position(accounts, params, cpi_executor)
+            }
+        }
+    }
+}
+
+// The referrer's token b ATA is only usable once it was created and while it isn't frozen
+fn is_referrer_token_account_usable(accounts: &WithdrawCommonAccounts) -> bool {
+    let referrer = accounts.referrer.to_account_info();
+    if *referrer.owner != accounts.token_b_program.key() {
+        return false;
+    }
+    let data = match referrer.try_borrow_data() {
+        Ok(data) => data,
+        Err(_) => return false,
+    };
+    match TokenAccount::try_deserialize(&mut &data[..]) {
+        Ok(referrer_token_account) => {
+            referrer_token_account.mint == accounts.vault.token_b_mint
+                && !referrer_token_account.is_frozen()
         }
+        Err(_) => false,
     }
 }
 
-fn execute_withdraw_b(
+pub(crate) fn execute_withdraw_b(
     accounts: &mut WithdrawCommonAccounts,
     cpi_executor: &mut impl CpiExecutor,
 ) -> Result<()> {
@@ -297,11 +513,24 @@ fn execute_withdraw_b(
         referrer_spread_amount_b,
         withdrawable_amount_b,
     } = get_withdrawal_amount_b(accounts);
+    // The referral cut goes to the treasury when the referrer can't receive it
+    let (treasury_spread_amount_b, referrer_spread_amount_b) =
+        if is_referrer_token_account_usable(accounts) {
+            (treasury_spread_amount_b, referrer_spread_amount_b)
+        } else {
+            (
+                treasury_spread_amount_b
+                    .checked_add(referrer_spread_amount_b)
+                    .unwrap(),
+                0,
+            )
+        };
     // If for some rounding reason we have 0 zero spread, don't error out
     let transfer_b_to_treasury = TransferToken::new(
-        &accounts.token_program,
+        &accounts.token_b_program,
+        &accounts.token_b_mint,
         &accounts.vault_token_b_account,
-        &accounts.vault_treasury_token_b_account,
+        &accounts.vault_treasury_token_b_account.to_account_info(),
         &accounts.vault.to_account_info(),
         treasury_spread_amount_b,
     );
@@ -313,9 +542,10 @@ fn execute_withdraw_b(
     };
 
     let transfer_b_to_referrer = TransferToken::new(
-        &accounts.token_program,
+        &accounts.token_b_program,
+        &accounts.token_b_mint,
         &accounts.vault_token_b_account,
-        &accounts.referrer,
+        &accounts.referrer.to_account_info(),
         &accounts.vault.to_account_info(),
         referrer_spread_amount_b,
     );
@@ -327,9 +557,10 @@ fn execute_withdraw_b(
     };
 
     let transfer_b_to_user = TransferToken::new(
-        &accounts.token_program,
+        &accounts.token_b_program,
+        &accounts.token_b_mint,
         &accounts.vault_token_b_account,
-        &accounts.user_token_b_account,
+        &accounts.user_token_b_account.to_account_info(),
         &accounts.vault.to_account_info(),
         withdrawable_amount_b,
     );
@@ -339,8 +570,13 @@ fn execute_withdraw_b(
     accounts
         .user_position
         .increase_withdrawn_amount(withdrawable_amount_b_before_fees);
+    if let Some(referrer_account) = &mut accounts.referrer_account {
+        referrer_account
+            .record_referral(withdrawable_amount_b_before_fees, referrer_spread_amount_b);
+    }
 
     /* MANUAL CPI (INTERACTIONS) */
+    let balance_b_before = accounts.vault_token_b_account.amount;
     let signer: &Vault = &accounts.vault;
 
     cpi_executor.execute_all(
@@ -352,40 +588,142 @@ fn execute_withdraw_b(
         signer,
     )?;
 
+    /* POST CPI VERIFICATION */
+    // Transfer fees are withheld from the recipients, so the vault always sends out the full amount
+    accounts.vault_token_b_account.reload()?;
+    validate!(
+        balance_b_before
+            .checked_sub(accounts.vault_token_b_account.amount)
+            .unwrap()
+            == withdrawable_amount_b_before_fees,
+        DripError::IncorrectWithdrawalAmount
+    );
+    let transfer_fee_b = calculate_transfer_fee(
+        &accounts.token_b_mint.to_account_info(),
+        withdrawable_amount_b,
+    )?;
+
+    emit!(WithdrawBEvent {
+        vault: accounts.vault.key(),
+        position: accounts.user_position.key(),
+        withdrawer: accounts.withdrawer.key(),
+        withdrawn_b: withdrawable_amount_b,
+        treasury_spread_b: treasury_spread_amount_b,
+        referrer_spread_b: referrer_spread_amount_b,
+        transfer_fee_b,
+    });
+
+    Ok(())
+}
+
+fn execute_decrease_position(
+    accounts: &mut DecreasePositionAccounts,
+    params: DecreasePositionParams,
+    cpi_executor: &mut impl CpiExecutor,
+) -> Result<()> {
+    // Settle the token B accrued so far, the position's drip schedule is restarted below
+    if get_withdrawable_amount_b_before_fees(&accounts.common) > 0 {
+        execute_withdraw_b(&mut accounts.common, cpi_executor)?;
+    }
+
+    /* COMPUTE (CHECKS) */
+    let withdrawable_amount_a = get_decrease_withdrawal_amount_a(&accounts.common, &params);
+
+    let transfer_a_to_user = TransferToken::new(
+        &accounts.token_a_program,
+        &accounts.token_a_mint,
+        &accounts.vault_token_a_account,
+        &accounts.user_token_a_account.to_account_info(),
+        &accounts.common.vault.to_account_info(),
+        withdrawable_amount_a,
+    );
+
+    /* STATE UPDATES (EFFECTS) */
+    reschedule_drip_amount(
+        &mut accounts.common.vault,
+        &mut accounts.vault_period_user_expiry,
+        &mut accounts.vault_period_new_expiry,
+        accounts.common.user_position.periodic_drip_amount,
+        params.periodic_drip_amount,
+    );
+
+    let last_drip_period = accounts.common.vault.last_drip_period;
+    accounts.common.user_position.reset_drip_schedule(
+        last_drip_period,
+        params.number_of_swaps,
+        params.periodic_drip_amount,
+    );
+
+    /* MANUAL CPI (INTERACTIONS) */
+    let signer: &Vault = &accounts.common.vault;
+    cpi_executor.execute_all(vec![&Some(&transfer_a_to_user)], signer)?;
+
+    emit!(PositionDecreased {
+        vault: accounts.common.vault.key(),
+        position: accounts.common.user_position.key(),
+        withdrawn_a: withdrawable_amount_a,
+        number_of_swaps: params.number_of_swaps,
+        periodic_drip_amount: params.periodic_drip_amount,
+        drip_period_id_before_deposit: last_drip_period,
+    });
+
     Ok(())
 }
 
-struct WithdrawalAmountB {
+// Positions starting at a period created before b_per_a_cumulative existed have no
+// accumulated value to diff against, they are paid out from the twap instead
+pub(crate) fn get_max_withdrawable_amount_b(
+    vault_period_i: &VaultPeriod,
+    vault_period_j: &VaultPeriod,
+    periodic_drip_amount: u64,
+    token_a_drip_trigger_spread: u16,
+) -> u64 {
+    if vault_period_i.has_b_per_a_cumulative {
+        calculate_withdraw_token_b_amount_from_b_per_a(
+            vault_period_i.b_per_a_cumulative,
+            vault_period_j.b_per_a_cumulative,
+            periodic_drip_amount,
+        )
+    } else {
+        calculate_withdraw_token_b_amount(
+            vault_period_i.period_id,
+            vault_period_j.period_id,
+            vault_period_i.twap,
+            vault_period_j.twap,
+            periodic_drip_amount,
+            token_a_drip_trigger_spread,
+        )
+    }
+}
+
+pub(crate) struct WithdrawalAmountB {
     pub withdrawable_amount_b_before_fees: u64,
     pub treasury_spread_amount_b: u64,
     pub referrer_spread_amount_b: u64,
     pub withdrawable_amount_b: u64,
 }
 
-fn get_withdrawal_amount_b(accounts: &WithdrawCommonAccounts) -> WithdrawalAmountB {
-    let i = accounts.vault_period_i.period_id;
-    let j = accounts.vault_period_j.period_id;
-    let max_withdrawable_amount_b = calculate_withdraw_token_b_amount(
-        i,
-        j,
-        accounts.vault_period_i.twap,
-        accounts.vault_period_j.twap,
+pub(crate) fn get_withdrawable_amount_b_before_fees(accounts: &WithdrawCommonAccounts) -> u64 {
+    let max_withdrawable_amount_b = get_max_withdrawable_amount_b(
+        &accounts.vault_period_i,
+        &accounts.vault_period_j,
         accounts.user_position.periodic_drip_amount,
-        accounts.vault_proto_config.token_a_drip_trigger_spread,
+        accounts.vault_proto_config.get_token_a_swap_spread(),
     );
-    let withdrawable_amount_b_before_fees = accounts
+    accounts
         .user_position
-        .get_withdrawable_amount_with_max(max_withdrawable_amount_b);
+        .get_withdrawable_amount_with_max(max_withdrawable_amount_b)
+}
+
+pub(crate) fn get_withdrawal_amount_b(accounts: &WithdrawCommonAccounts) -> WithdrawalAmountB {
+    let (token_b_withdrawal_spread, token_b_referral_spread) = get_withdrawal_spreads(accounts);
+    let withdrawable_amount_b_before_fees = get_withdrawable_amount_b_before_fees(accounts);
 
     // Account for Withdrawal Spread on Token B
-    let treasury_spread_amount_b = calculate_spread_amount(
-        withdrawable_amount_b_before_fees,
-        accounts.vault_proto_config.token_b_withdrawal_spread,
-    );
-    let referrer_spread_amount_b = calculate_spread_amount(
-        withdrawable_amount_b_before_fees,
-        accounts.vault_proto_config.token_b_referral_spread,
-    );
+    let treasury_spread_amount_b =
+        calculate_spread_amount(withdrawable_amount_b_before_fees, token_b_withdrawal_spread);
+    let referrer_spread_amount_b =
+        calculate_spread_amount(withdrawable_amount_b_before_fees, token_b_referral_spread);
 
     let withdrawable_amount_b = withdrawable_amount_b_before_fees
         .checked_sub(treasury_spread_amount_b)
@@ -401,6 +739,61 @@ fn get_withdrawal_amount_b(accounts: &WithdrawCommonAccounts) -> WithdrawalAmoun
     }
 }
 
+// Returns (token_b_withdrawal_spread, token_b_referral_spread)
+fn get_withdrawal_spreads(accounts: &WithdrawCommonAccounts) -> (u16, u16) {
+    resolve_withdrawal_spreads(
+        &accounts.vault_proto_config,
+        &accounts.user_position,
+        accounts
+            .referrer_account
+            .as_deref()
+            .map(|referrer| &**referrer),
+        Clock::get().unwrap().unix_timestamp,
+    )
+}
+
+// The withdrawal spread follows the proto config's policy. The referral spread of a position
+// whose Referrer is passed is always the Referrer's, under either policy, so it matches the
+// referral stats recorded on it, capped in case the other spreads were raised after it was set
+fn resolve_withdrawal_spreads(
+    vault_proto_config: &VaultProtoConfig,
+    user_position: &Position,
+    referrer: Option<&Referrer>,
+    timestamp: i64,
+) -> (u16, u16) {
+    let (
+        token_a_drip_trigger_spread,
+        current_token_b_withdrawal_spread,
+        current_token_b_referral_spread,
+    ) = vault_proto_config.get_spreads_at(timestamp);
+    let (token_b_withdrawal_spread, token_b_referral_spread) =
+        if vault_proto_config.withdrawal_spread_policy == WithdrawalSpreadPolicy::AtDeposit
+            && user_position.has_recorded_spreads
+        {
+            (
+                user_position.token_b_withdrawal_spread,
+                user_position.token_b_referral_spread,
+            )
+        } else {
+            (
+                current_token_b_withdrawal_spread,
+                current_token_b_referral_spread,
+            )
+        };
+
+    match referrer {
+        Some(referrer) => (
+            token_b_withdrawal_spread,
+            VaultProtoConfig::clamp_referral_spread(
+                token_a_drip_trigger_spread,
+                token_b_withdrawal_spread,
+                referrer.token_b_referral_spread,
+            ),
+        ),
+        None => (token_b_withdrawal_spread, token_b_referral_spread),
+    }
+}
+
 fn get_withdrawal_amount_a(accounts: &WithdrawCommonAccounts) -> u64 {
     let i = accounts.vault_period_i.period_id;
     let j = accounts.vault_period_j.period_id;
@@ -412,3 +805,225 @@ fn get_withdrawal_amount_a(accounts: &WithdrawCommonAccounts) -> u64 {
         accounts.user_position.periodic_drip_amount,
     )
 }
+
+fn get_remaining_swaps(accounts: &WithdrawCommonAccounts) -> u64 {
+    calculate_remaining_swaps(
+        accounts.vault_period_i.period_id,
+        accounts.vault_period_j.period_id,
+        accounts.user_position.number_of_swaps,
+    )
+}
+
+// Token A that is no longer needed after decreasing the position to the new drip schedule
+fn get_decrease_withdrawal_amount_a(
+    accounts: &WithdrawCommonAccounts,
+    params: &DecreasePositionParams,
+) -> u64 {
+    let remaining_amount_a = get_withdrawal_amount_a(accounts);
+    let new_remaining_amount_a = params
+        .number_of_swaps
+        .checked_mul(params.periodic_drip_amount)
+        .unwrap();
+
+    remaining_amount_a.saturating_sub(new_remaining_amount_a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::DripError::{
+        InvalidPositionDecrease, InvalidVaultPeriod, NumSwapsIsZero, PeriodicDripAmountIsZero,
+        WithdrawableAmountIsZero,
+    };
+    use crate::interactions::executor::test::TestCpiExecutor;
+    use crate::interactions::executor::CpiIdentifier;
+    use crate::state::Position;
+    use crate::test::fixtures::{
+        UpdatePositionFixtures, TOKEN_A_MINT, VAULT, VAULT_TOKEN_A_ACCOUNT,
+    };
+    use anchor_spl::token::Token;
+    use test_case::test_case;
+
+    const NUMBER_OF_SWAPS: u64 = 4;
+    const PERIODIC_DRIP_AMOUNT: u64 = 10;
+
+    fn new_vault_period(
+        period_id: u64,
+        twap: u128,
+        b_per_a_cumulative: u128,
+        has_b_per_a_cumulative: bool,
+    ) -> VaultPeriod {
+        VaultPeriod {
+            period_id,
+            twap,
+            b_per_a_cumulative,
+            has_b_per_a_cumulative,
+            ..VaultPeriod::default()
+        }
+    }
+
+    #[test_case(true, 0, 3 << 64, 10, 30; "Uses b_per_a_cumulative")]
+    #[test_case(false, 0, 3 << 64, 10, 20; "Uses the twap for periods without b_per_a_cumulative")]
+    #[test_case(false, 5000, 3 << 64, 10, 10; "Removes the drip trigger spread from the twap")]
+    fn get_max_withdrawable_amount_b_tests(
+        has_b_per_a_cumulative: bool,
+        token_a_drip_trigger_spread: u16,
+        b_per_a_cumulative_j: u128,
+        periodic_drip_amount: u64,
+        expected: u64,
+    ) {
+        // Two periods at a price of 1, b_per_a_cumulative differs from the twap on purpose
+        let vault_period_i = new_vault_period(1, 1 << 64, 0, has_b_per_a_cumulative);
+        let vault_period_j = new_vault_period(3, 1 << 64, b_per_a_cumulative_j, true);
+        assert_eq!(
+            get_max_withdrawable_amount_b(
+                &vault_period_i,
+                &vault_period_j,
+                periodic_drip_amount,
+                token_a_drip_trigger_spread
+            ),
+            expected
+        );
+    }
+
+    #[test_case(WithdrawalSpreadPolicy::Current, true, None, (50, 10); "Uses the current spreads")]
+    #[test_case(WithdrawalSpreadPolicy::Current, true, Some(20), (50, 20); "Uses the referrer's spread with the current spreads")]
+    #[test_case(WithdrawalSpreadPolicy::AtDeposit, true, None, (30, 5); "Uses the recorded spreads")]
+    #[test_case(WithdrawalSpreadPolicy::AtDeposit, true, Some(20), (30, 20); "Uses the referrer's spread with the recorded spreads")]
+    #[test_case(WithdrawalSpreadPolicy::AtDeposit, false, None, (50, 10); "Uses the current spreads for positions without recorded spreads")]
+    #[test_case(WithdrawalSpreadPolicy::AtDeposit, true, Some(4990), (30, 4919); "Caps the referrer's spread against the recorded spreads")]
+    fn resolve_withdrawal_spreads_tests(
+        withdrawal_spread_policy: WithdrawalSpreadPolicy,
+        has_recorded_spreads: bool,
+        token_b_referral_spread: Option<u16>,
+        expected: (u16, u16),
+    ) {
+        let vault_proto_config = VaultProtoConfig {
+            token_a_drip_trigger_spread: 50,
+            token_b_withdrawal_spread: 50,
+            token_b_referral_spread: 10,
+            withdrawal_spread_policy,
+            ..VaultProtoConfig::default()
+        };
+        let user_position = Position {
+            token_b_withdrawal_spread: 30,
+            token_b_referral_spread: 5,
+            has_recorded_spreads,
+            ..Position::default()
+        };
+        let referrer = token_b_referral_spread.map(|token_b_referral_spread| Referrer {
+            token_b_referral_spread,
+            ..Referrer::default()
+        });
+        assert_eq!(
+            resolve_withdrawal_spreads(&vault_proto_config, &user_position, referrer.as_ref(), 0),
+            expected
+        );
+    }
+
+    #[test_case(2, 0, 5, 2, Err(NumSwapsIsZero.into()); "Returns error for zero swaps")]
+    #[test_case(2, 1, 0, 3, Err(PeriodicDripAmountIsZero.into()); "Returns error for a zero periodic drip amount")]
+    #[test_case(2, 3, 5, 5, Err(InvalidPositionDecrease.into()); "Returns error for more swaps than remain")]
+    #[test_case(2, 2, 11, 4, Err(InvalidPositionDecrease.into()); "Returns error for a larger periodic drip amount")]
+    #[test_case(4, 1, 5, 5, Err(InvalidPositionDecrease.into()); "Returns error for a position that just swapped for the last time")]
+    #[test_case(5, 1, 5, 6, Err(InvalidPositionDecrease.into()); "Returns error for a position expired before the last drip")]
+    #[test_case(2, 2, 10, 4, Err(WithdrawableAmountIsZero.into()); "Returns error when the schedule doesn't change")]
+    #[test_case(2, 1, 10, 4, Err(InvalidVaultPeriod.into()); "Returns error when the new expiry period doesn't match the new schedule")]
+    #[test_case(2, 1, 10, 3, Ok(()); "Returns ok for fewer swaps")]
+    #[test_case(2, 2, 5, 4, Ok(()); "Returns ok for a smaller periodic drip amount")]
+    #[test_case(0, 4, 5, 4, Ok(()); "Returns ok for a position that hasn't dripped yet")]
+    fn decrease_position_validate(
+        last_drip_period: u64,
+        number_of_swaps: u64,
+        periodic_drip_amount: u64,
+        new_expiry_period_id: u64,
+        expected_res: Result<()>,
+    ) {
+        let mut fixtures = UpdatePositionFixtures::new(
+            NUMBER_OF_SWAPS,
+            PERIODIC_DRIP_AMOUNT,
+            last_drip_period,
+            new_expiry_period_id,
+        );
+        let account_infos = fixtures.to_account_infos();
+        let accounts = &mut account_infos.to_decrease_position_accounts();
+
+        let decrease_position_action = Withdraw::DecreasePosition {
+            accounts,
+            params: DecreasePositionParams {
+                number_of_swaps,
+                periodic_drip_amount,
+            },
+        };
+        assert_eq!(decrease_position_action.validate(), expected_res);
+    }
+
+    #[test_case(2, 1, 10, 10, 0, 10; "Returns the token a of the dropped swaps")]
+    #[test_case(2, 2, 5, 10, 5, 5; "Reschedules the drip amount within the same vault period when the swaps don't change")]
+    #[test_case(0, 4, 5, 20, 5, 5; "Decreases a position that hasn't dripped yet")]
+    #[test_case(1, 1, 1, 29, 0, 1; "Keeps only the new schedule's token a in the vault")]
+    fn decrease_position_happy_path(
+        last_drip_period: u64,
+        number_of_swaps: u64,
+        periodic_drip_amount: u64,
+        expected_withdrawn_amount_a: u64,
+        expected_user_expiry_dar: u64,
+        expected_new_expiry_dar: u64,
+    ) {
+        let new_expiry_period_id = last_drip_period + number_of_swaps;
+        let mut fixtures = UpdatePositionFixtures::new(
+            NUMBER_OF_SWAPS,
+            PERIODIC_DRIP_AMOUNT,
+            last_drip_period,
+            new_expiry_period_id,
+        );
+        let account_infos = fixtures.to_account_infos();
+        let accounts = &mut account_infos.to_decrease_position_accounts();
+        let user_token_a_account = accounts.user_token_a_account.key();
+        let mut cpi_executor = TestCpiExecutor {
+            cpi_calls: vec![],
+            signer: None,
+        };
+
+        let decrease_position_action = Withdraw::DecreasePosition {
+            accounts,
+            params: DecreasePositionParams {
+                number_of_swaps,
+                periodic_drip_amount,
+            },
+        };
+        assert_eq!(decrease_position_action.validate(), Ok(()));
+        decrease_position_action.execute(&mut cpi_executor).unwrap();
+
+        assert_eq!(
+            cpi_executor.cpi_calls,
+            vec![CpiIdentifier::TransferToken {
+                token_program: Token::id(),
+                mint: TOKEN_A_MINT.key,
+                from: VAULT_TOKEN_A_ACCOUNT.key,
+                to: user_token_a_account,
+                authority: VAULT.key,
+                amount: expected_withdrawn_amount_a,
+            }]
+        );
+
+        let user_position = &accounts.common.user_position;
+        assert_eq!(
+            user_position.drip_period_id_before_deposit,
+            last_drip_period
+        );
+        assert_eq!(user_position.number_of_swaps, number_of_swaps);
+        assert_eq!(user_position.periodic_drip_amount, periodic_drip_amount);
+        assert_eq!(user_position.withdrawn_token_b_amount, 0);
+
+        assert_eq!(accounts.common.vault.drip_amount, periodic_drip_amount);
+        assert_eq!(
+            accounts.vault_period_user_expiry.dar,
+            expected_user_expiry_dar
+        );
+        assert_eq!(
+            accounts.vault_period_new_expiry.dar,
+            expected_new_expiry_dar
+        );
+    }
+}
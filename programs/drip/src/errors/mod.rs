@@ -63,4 +63,82 @@ pub enum DripError {
     InvalidSolDestination,
     #[msg("Position is not closed")]
     PositionIsNotClosed,
+    #[msg("Position can only be decreased to a smaller non-zero drip schedule")]
+    InvalidPositionDecrease,
+    #[msg("Drips are paused for this vault")]
+    VaultIsPaused,
+    #[msg("Deposits are paused for this vault")]
+    VaultDepositsArePaused,
+    #[msg("Withdrawals are paused for this vault")]
+    VaultWithdrawalsArePaused,
+    #[msg("Signer is not the pending admin")]
+    SignerIsNotPendingAdmin,
+    #[msg("Spreads must be scheduled at least the minimum notice period in advance")]
+    InvalidSpreadEffectiveTimestamp,
+    #[msg("Sum of the spreads must be <5000")]
+    InvalidTotalSpread,
+    #[msg("Received less than the minimum amount out from the swap")]
+    InsufficientSwapOutput,
+    #[msg("Vault token account authority was modified during the swap")]
+    VaultTokenAccountModified,
+    #[msg("Oracle account is invalid or does not match the vault's oracle")]
+    InvalidOracleAccount,
+    #[msg("Oracle price is unavailable")]
+    InvalidOraclePrice,
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    #[msg("Drip price deviates too far from the oracle price")]
+    OraclePriceDeviationExceeded,
+    #[msg("Oracle deviation must be >0 and <10000")]
+    InvalidOracleDeviation,
+    #[msg("Invalid intermediate token account")]
+    InvalidIntermediateTokenAccount,
+    #[msg("Intermediate token account was not fully swapped")]
+    IntermediateBalanceNotSwapped,
+    #[msg("Drip split legs must be non-empty and add up to the swap amount")]
+    InvalidDripSplit,
+    #[msg("Keeper reward lamports must be set only for the lamports reward type")]
+    InvalidKeeperReward,
+    #[msg("Drip fee token account mint does not match the keeper reward token")]
+    InvalidDripFeeTokenAccount,
+    #[msg("Keeper whitelist is too large or contains the default pubkey")]
+    InvalidNumKeepers,
+    #[msg("Drip trigger source is not a whitelisted keeper")]
+    KeeperNotWhitelisted,
+    #[msg("Drip execution window must be shorter than the granularity")]
+    InvalidDripExecutionWindow,
+    #[msg("Drip is outside the execution window of the current period")]
+    DripExecutionWindowMissed,
+    #[msg("Catch up drips need the catch up policy and consecutive writable vault periods")]
+    InvalidCatchUpDrip,
+    #[msg("Vault period already has the current account size")]
+    VaultPeriodAlreadyReallocated,
+    #[msg("Positions drip more token A per period than the vault drip amount")]
+    PositionsExceedVaultDripAmount,
+    #[msg("Vault token A balance does not cover the remaining drips")]
+    InsufficientVaultTokenABalance,
+    #[msg("Vault token B balance does not cover the withdrawable token B")]
+    InsufficientVaultTokenBBalance,
+    #[msg("Vault period needed for the invariant check was not provided")]
+    MissingVaultPeriod,
+    #[msg("Vault token balance did not decrease by the withdrawn amount")]
+    IncorrectWithdrawalAmount,
+    #[msg("Token account is not a native SOL account owned by the signer")]
+    InvalidNativeTokenAccount,
+    #[msg("Signer is neither the admin nor the position referrer")]
+    SignerIsNotAdminOrReferrer,
+    #[msg("Minimum swap output could not be computed")]
+    InvalidMinimumOut,
+    #[msg("Drips through a caller supplied route need the vault's price oracle")]
+    MissingOraclePrice,
+    #[msg("Swap pool curve type is not supported")]
+    UnsupportedCurveType,
+    #[msg("Swap pool reserves are empty")]
+    EmptySwapReserves,
+    #[msg("Swap venue does not support token-2022 mints")]
+    UnsupportedTokenProgram,
+    #[msg("Transfer fee could not be computed")]
+    InvalidTransferFee,
+    #[msg("User token account is required for this deposit")]
+    MissingUserTokenAccount,
 }
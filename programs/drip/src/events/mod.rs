@@ -1,8 +1,241 @@
+use crate::state::{KeeperRewardType, MissedDripPolicy, WithdrawalSpreadPolicy};
 use anchor_lang::prelude::*;
 
 #[event]
-pub struct Log {
-    pub data: Option<u64>,
+pub struct VaultProtoConfigInitialized {
+    pub vault_proto_config: Pubkey,
+    pub admin: Pubkey,
+    pub granularity: u64,
+    pub token_a_drip_trigger_spread: u16,
+    pub token_b_withdrawal_spread: u16,
+    pub token_b_referral_spread: u16,
+    pub keeper_reward_type: KeeperRewardType,
+    pub keeper_reward_lamports: u64,
+}
+
+#[event]
+pub struct AdminProposed {
+    #[index]
+    pub vault_proto_config: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminAccepted {
+    #[index]
+    pub vault_proto_config: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct SpreadsUpdateScheduled {
+    #[index]
+    pub vault_proto_config: Pubkey,
+    pub token_a_drip_trigger_spread: u16,
+    pub token_b_withdrawal_spread: u16,
+    pub token_b_referral_spread: u16,
+    pub effective_timestamp: i64,
+    pub withdrawal_spread_policy: WithdrawalSpreadPolicy,
+}
+
+#[event]
+pub struct VaultPeriodInitialized {
+    #[index]
+    pub vault: Pubkey,
+    pub vault_period: Pubkey,
+    pub period_id: u64,
+}
+
+#[event]
+pub struct VaultInitialized {
+    #[index]
+    pub vault: Pubkey,
+    pub vault_proto_config: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub treasury_token_b_account: Pubkey,
+    pub max_slippage_bps: u16,
+    pub drip_activation_timestamp: i64,
+}
+
+#[event]
+pub struct DepositEvent {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub depositor: Pubkey,
+    pub referrer: Pubkey,
+    pub token_a_deposit_amount: u64,
+    pub number_of_swaps: u64,
+    pub periodic_drip_amount: u64,
+    // The drip period ID of the vault that happened prior to the deposit
+    pub drip_period_id_before_deposit: u64,
+}
+
+#[event]
+pub struct PositionIncreased {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub token_a_deposit_amount: u64,
+    // Swaps remaining in the position after the increase
+    pub number_of_swaps: u64,
+    pub periodic_drip_amount: u64,
+    pub drip_period_id_before_deposit: u64,
+}
+
+#[event]
+pub struct DripEvent {
+    #[index]
+    pub vault: Pubkey,
+    // Catch up drips process every period from first_period_id to period_id
+    pub first_period_id: u64,
+    pub period_id: u64,
+    pub drip_trigger_source: Pubkey,
+    // The swap program and the pool/whirlpool account that was used for the swap
+    pub swap_program: Pubkey,
+    pub swap: Pubkey,
+    // Token A sent into the swap (excludes the drip trigger spread)
+    pub sent_a: u64,
+    pub received_b: u64,
+    // Keeper reward sent to the drip trigger source, only one of these is non-zero
+    pub drip_trigger_spread_a: u64,
+    pub drip_trigger_spread_b: u64,
+    pub drip_trigger_reward_lamports: u64,
+    pub twap: u128,
+    pub drip_timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawBEvent {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub withdrawer: Pubkey,
+    // Amount sent to the user, after spreads
+    pub withdrawn_b: u64,
+    pub treasury_spread_b: u64,
+    pub referrer_spread_b: u64,
+    // Part of withdrawn_b withheld by the token b transfer fee
+    pub transfer_fee_b: u64,
+}
+
+#[event]
+pub struct PositionDecreased {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub withdrawn_a: u64,
+    // Swaps remaining in the position after the decrease
+    pub number_of_swaps: u64,
+    pub periodic_drip_amount: u64,
+    pub drip_period_id_before_deposit: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub withdrawer: Pubkey,
+    pub withdrawn_a: u64,
+    // True if the position was closed by the admin on behalf of the position owner
+    pub closed_by_admin: bool,
+}
+
+#[event]
+pub struct PositionAccountClosed {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub sol_destination: Pubkey,
+}
+
+#[event]
+pub struct WhitelistUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub whitelisted_swaps: Vec<Pubkey>,
+    pub limit_swaps: bool,
+}
+
+#[event]
+pub struct KeeperWhitelistUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub whitelisted_keepers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct VaultDripScheduleUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub drip_execution_window: u64,
+    pub missed_drip_policy: MissedDripPolicy,
+    pub drip_activation_timestamp: i64,
+}
+
+#[event]
+pub struct VaultPauseUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub paused: bool,
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
+}
+
+#[event]
+pub struct VaultOracleUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub oracle: Pubkey,
+    pub max_oracle_deviation_bps: u16,
+}
+
+#[event]
+pub struct AdminWithdrawEvent {
+    #[index]
+    pub vault: Pubkey,
+    pub admin: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionReferrerUpdated {
+    #[index]
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub signer: Pubkey,
+    pub old_referrer: Pubkey,
+    pub new_referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferrerInitialized {
+    #[index]
+    pub vault_proto_config: Pubkey,
+    pub referrer: Pubkey,
+    pub token_b_referral_spread: u16,
+}
+
+#[event]
+pub struct ReferrerSpreadUpdated {
+    #[index]
+    pub vault_proto_config: Pubkey,
+    pub referrer: Pubkey,
+    pub old_token_b_referral_spread: u16,
+    pub new_token_b_referral_spread: u16,
+}
+
+#[event]
+pub struct ReferrerClosed {
     #[index]
-    pub message: String,
+    pub vault_proto_config: Pubkey,
+    pub referrer: Pubkey,
+    pub sol_destination: Pubkey,
 }
@@ -1,7 +1,9 @@
-use crate::state::{Position, Vault, VaultProtoConfig};
+use crate::state::{
+    MissedDripPolicy, Position, Referrer, Vault, VaultProtoConfig, WithdrawalSpreadPolicy,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeVaultParams {
@@ -38,27 +40,31 @@ pub struct InitializeVaultAccounts<'info> {
         init,
         associated_token::mint = token_a_mint,
         associated_token::authority = vault,
+        associated_token::token_program = token_a_program,
         payer = creator
     )]
-    pub token_a_account: Box<Account<'info, TokenAccount>>,
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
         associated_token::mint = token_b_mint,
         associated_token::authority = vault,
+        associated_token::token_program = token_b_program,
         payer = creator,
     )]
-    pub token_b_account: Box<Account<'info, TokenAccount>>,
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub treasury_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub treasury_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /* MINTS */
-    pub token_a_mint: Box<Account<'info, Mint>>,
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_b_mint: Box<Account<'info, Mint>>,
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /* MISC */
-    pub token_program: Program<'info, Token>,
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    pub token_b_program: Interface<'info, TokenInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
 
@@ -85,6 +91,127 @@ pub struct UpdateVaultWhitelistedSwapsAccounts<'info> {
     pub vault_proto_config: Account<'info, VaultProtoConfig>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetVaultKeeperWhitelistParams {
+    // An empty list lets anyone trigger drips
+    pub whitelisted_keepers: Vec<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultKeeperWhitelistAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetVaultDripScheduleParams {
+    // 0 lets the vault be dripped at any time after activation
+    pub drip_execution_window: u64,
+    pub missed_drip_policy: MissedDripPolicy,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultDripScheduleAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetVaultPausedParams {
+    pub paused: bool,
+    // Deposits and withdrawals stay open unless explicitly paused as well
+    pub pause_deposits: bool,
+    pub pause_withdrawals: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultPausedAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetVaultOracleParams {
+    // Ignored when the oracle is removed
+    pub max_oracle_deviation_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultOracleAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated as an oracle price account, None removes the vault's oracle
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAdminParams {
+    // Pubkey::default() cancels a pending proposal
+    pub new_admin: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminAccounts<'info> {
+    pub pending_admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateVaultProtoConfigSpreadsParams {
+    pub token_a_drip_trigger_spread: u16,
+    pub token_b_withdrawal_spread: u16,
+    pub token_b_referral_spread: u16,
+    // Timestamp from which the new spreads are applied
+    pub effective_timestamp: i64,
+    pub withdrawal_spread_policy: WithdrawalSpreadPolicy,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVaultProtoConfigSpreadsAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawAAccounts<'info> {
     #[account(mut)]
@@ -94,15 +221,17 @@ pub struct WithdrawAAccounts<'info> {
 
     // mut needed because we are changing state
     #[account(mut)]
-    pub vault_token_a_account: Account<'info, TokenAccount>,
+    pub vault_token_a_account: InterfaceAccount<'info, TokenAccount>,
 
     // mut needed because we are changing state
     #[account(mut)]
-    pub admin_token_a_account: Account<'info, TokenAccount>,
+    pub admin_token_a_account: InterfaceAccount<'info, TokenAccount>,
 
     pub vault_proto_config: Account<'info, VaultProtoConfig>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_a_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -115,13 +244,16 @@ pub struct AdminWithdrawAccounts<'info> {
 
     // mut needed because we are changing state
     #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
 
     // mut needed because we are changing state
     #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    // Mint of the vault token account, needed for transfer_checked
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -139,3 +271,86 @@ pub struct ClosePositionAccountAccounts<'info> {
     /// CHECK: We don't care what this account is
     pub sol_destination: AccountInfo<'info>,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateReferrerParams {
+    // Referral fees go to the token b ATA of this wallet
+    pub referrer: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReferrerAccounts<'info> {
+    // Either the admin or the position's current referrer wallet
+    pub signer: Signer<'info>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+
+    pub vault: Account<'info, Vault>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeReferrerParams {
+    pub token_b_referral_spread: u16,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReferrerAccounts<'info> {
+    // mut needed because we are initializing an account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+
+    /// CHECK: The referrer wallet, it doesn't need to sign
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        space = Referrer::ACCOUNT_SPACE,
+        seeds = [
+            b"referrer".as_ref(),
+            vault_proto_config.key().as_ref(),
+            referrer.key().as_ref()
+        ],
+        bump,
+        payer = admin
+    )]
+    pub referrer_account: Account<'info, Referrer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateReferrerSpreadParams {
+    pub token_b_referral_spread: u16,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReferrerSpreadAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub referrer_account: Account<'info, Referrer>,
+}
+
+#[derive(Accounts)]
+pub struct CloseReferrerAccounts<'info> {
+    pub admin: Signer<'info>,
+
+    pub vault_proto_config: Account<'info, VaultProtoConfig>,
+
+    // mut needed because we are closing the account
+    #[account(mut)]
+    pub referrer_account: Account<'info, Referrer>,
+
+    #[account(mut)]
+    /// CHECK: We don't care what this account is
+    pub sol_destination: AccountInfo<'info>,
+}
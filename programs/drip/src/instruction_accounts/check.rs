@@ -0,0 +1,16 @@
+use crate::state::{Vault, VaultProtoConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// Positions and vault periods to check are passed as remaining accounts
+#[derive(Accounts)]
+pub struct CheckVaultInvariantsAccounts<'info> {
+    pub vault: Box<Account<'info, Vault>>,
+
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub vault_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Needed for the drip trigger spread of positions paid out from the twap
+    pub vault_proto_config: Box<Account<'info, VaultProtoConfig>>,
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::instruction_accounts::withdraw::*;
 use crate::interactions::create_token_metadata::MetaplexTokenMetadata;
-use crate::state::{Position, Vault, VaultPeriod};
+use crate::state::{Position, Vault, VaultPeriod, VaultProtoConfig};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DepositParams {
@@ -21,17 +22,22 @@ pub struct DepositCommonAccounts<'info> {
     #[account(mut)]
     pub vault: Box<Account<'info, Vault>>,
 
+    // Used to record the spreads in effect at the time of deposit
+    // and for the granularity when restarting an idle vault's drip schedule
+    pub vault_proto_config: Box<Account<'info, VaultProtoConfig>>,
+
     // mut reason: modifying state
     #[account(mut)]
     pub vault_period_end: Box<Account<'info, VaultPeriod>>,
 
     // mut reason: changing balance
     #[account(mut)]
-    pub vault_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // Not used by SOL deposits, which wrap into a temporary account instead
     // mut reason: changing balance
     #[account(mut)]
-    pub user_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_a_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         init,
@@ -52,7 +58,7 @@ pub struct DepositCommonAccounts<'info> {
         mint::decimals = 0,
         payer = depositor
     )]
-    pub user_position_nft_mint: Box<Account<'info, Mint>>,
+    pub user_position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init,
@@ -60,11 +66,17 @@ pub struct DepositCommonAccounts<'info> {
         associated_token::authority = depositor,
         payer = depositor
     )]
-    pub user_position_nft_account: Box<Account<'info, TokenAccount>>,
+    pub user_position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The owner of this token b account is stored as the position's referrer
+    pub referrer: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub referrer: Box<Account<'info, TokenAccount>>,
+    // Needed for transfer_checked
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    // Token program of the position NFT
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_a_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
@@ -84,3 +96,91 @@ pub struct DepositWithMetadataAccounts<'info> {
     pub position_metadata_account: UncheckedAccount<'info>,
     pub metadata_program: Program<'info, MetaplexTokenMetadata>,
 }
+
+#[derive(Accounts)]
+pub struct DepositSolAccounts<'info> {
+    pub common: DepositCommonAccounts<'info>,
+
+    // Holds the wrapped SOL for the duration of the deposit, closed back to the depositor
+    #[account(
+        init,
+        seeds = [
+            b"temporary_wsol".as_ref(),
+            common.depositor.key().as_ref()
+        ],
+        bump,
+        payer = common.depositor,
+        token::mint = common.token_a_mint,
+        token::authority = common.depositor,
+        token::token_program = token_a_program,
+    )]
+    pub temporary_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Anchor's init only looks for these programs on this struct
+    #[account(address = common.token_a_program.key())]
+    pub token_a_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSolWithMetadataAccounts<'info> {
+    pub common: DepositCommonAccounts<'info>,
+
+    // Holds the wrapped SOL for the duration of the deposit, closed back to the depositor
+    #[account(
+        init,
+        seeds = [
+            b"temporary_wsol".as_ref(),
+            common.depositor.key().as_ref()
+        ],
+        bump,
+        payer = common.depositor,
+        token::mint = common.token_a_mint,
+        token::authority = common.depositor,
+        token::token_program = token_a_program,
+    )]
+    pub temporary_wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Anchor's init only looks for these programs on this struct
+    #[account(address = common.token_a_program.key())]
+    pub token_a_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Checked by metaplex's program
+    #[account(mut)]
+    pub position_metadata_account: UncheckedAccount<'info>,
+    pub metadata_program: Program<'info, MetaplexTokenMetadata>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct IncreasePositionParams {
+    pub token_a_deposit_amount: u64,
+    // Swaps added on top of the swaps remaining in the position
+    pub number_of_swaps: u64,
+}
+
+#[derive(Accounts)]
+pub struct IncreasePositionAccounts<'info> {
+    // Accrued token B is withdrawn before the position is increased
+    pub common: WithdrawCommonAccounts<'info>,
+
+    // mut reason: modifying state
+    #[account(mut)]
+    pub vault_period_user_expiry: Box<Account<'info, VaultPeriod>>,
+
+    // mut reason: modifying state
+    #[account(mut)]
+    pub vault_period_new_expiry: Box<Account<'info, VaultPeriod>>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub user_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_a_program: Interface<'info, TokenInterface>,
+}
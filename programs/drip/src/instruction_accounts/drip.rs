@@ -1,7 +1,7 @@
 use crate::state::{Vault, VaultPeriod, VaultProtoConfig};
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use whirlpool::state::Whirlpool;
 
 #[derive(Clone)]
@@ -22,9 +22,20 @@ impl Id for WhirlpoolProgram {
     }
 }
 
+#[derive(Clone)]
+pub struct RaydiumAmmProgram;
+
+impl Id for RaydiumAmmProgram {
+    fn id() -> Pubkey {
+        anchor_lang::solana_program::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8")
+    }
+}
+
 #[derive(Accounts)]
 pub struct DripCommonAccounts<'info> {
     // User that triggers the Drip
+    // mut reason: receives the keeper reward when it is paid in lamports
+    #[account(mut)]
     pub drip_trigger_source: Signer<'info>,
 
     // mut reason: changing state
@@ -41,25 +52,80 @@ pub struct DripCommonAccounts<'info> {
 
     // mut reason: changing balance
     #[account(mut)]
-    pub vault_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut reason: changing balance
     #[account(mut)]
-    pub vault_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut reason: changing balance
     #[account(mut)]
-    pub swap_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub swap_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut reason: changing balance
     #[account(mut)]
-    pub swap_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub swap_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // Receives the keeper reward, a token a or token b account depending on the
+    // proto config's keeper_reward_type
     // mut reason: changing balance
     #[account(mut)]
-    pub drip_fee_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub drip_fee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Needed for transfer_checked
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Token program of each mint, either may be token-2022
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+pub const MAX_DRIP_SPLIT_LEGS: usize = 4;
+// Vault periods after current_vault_period that a single catch up drip can process
+pub const MAX_CATCH_UP_PERIODS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapVenue {
+    // [token_swap_program, swap, swap_authority, swap_token_a_account, swap_token_b_account,
+    //  swap_token_mint, swap_fee_account]
+    SPLTokenSwap,
+    // [whirlpool_program, whirlpool, token_vault_a, token_vault_b, tick_array_0, tick_array_1,
+    //  tick_array_2, oracle]
+    OrcaWhirlpool,
+}
+
+impl SwapVenue {
+    // Number of remaining accounts consumed by a leg through this venue
+    pub fn leg_account_count(&self) -> usize {
+        match self {
+            SwapVenue::SPLTokenSwap => 7,
+            SwapVenue::OrcaWhirlpool => 8,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DripSplitLeg {
+    pub venue: SwapVenue,
+    pub amount_in: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DripSplitParams {
+    // amount_in of the legs must add up to the drip amount less the drip trigger spread,
+    // each leg's accounts are passed as remaining accounts in the same order as the legs
+    pub legs: Vec<DripSplitLeg>,
+}
 
-    pub token_program: Program<'info, Token>,
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DripAggregatorParams {
+    // Instruction data passed through to the router program
+    pub swap_data: Vec<u8>,
+    // Raised to the oracle price less max_slippage_bps if lower
+    pub minimum_amount_out: u64,
 }
 
 #[derive(Accounts)]
@@ -71,16 +137,19 @@ pub struct DripSPLTokenSwapAccounts<'info> {
 
     // mut reason: CPI
     #[account(mut)]
-    pub swap_token_mint: Box<Account<'info, Mint>>,
+    pub swap_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     // mut reason: changing balance
     #[account(mut)]
-    pub swap_fee_account: Box<Account<'info, TokenAccount>>,
+    pub swap_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Checked by token-swap program
     pub swap_authority: UncheckedAccount<'info>,
 
     pub token_swap_program: Program<'info, TokenSwap>,
+
+    /// CHECK: Must match the vault's oracle, required only if the vault has one
+    pub price_oracle: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
@@ -107,4 +176,148 @@ pub struct DripOrcaWhirlpoolAccounts<'info> {
     pub oracle: UncheckedAccount<'info>,
 
     pub whirlpool_program: Program<'info, WhirlpoolProgram>,
+
+    /// CHECK: Must match the vault's oracle, required only if the vault has one
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct DripAggregatorAccounts<'info> {
+    pub common: DripCommonAccounts<'info>,
+
+    /// CHECK: Must be whitelisted on the vault, the route's accounts are passed as remaining accounts
+    #[account(executable)]
+    pub router_program: UncheckedAccount<'info>,
+
+    /// CHECK: Must match the vault's oracle, aggregator drips always require one.
+    /// Passed before the route's remaining accounts
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct DripRaydiumAccounts<'info> {
+    pub common: DripCommonAccounts<'info>,
+
+    // mut reason: CPI
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub amm: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Raydium
+    pub amm_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub amm_open_orders: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub amm_target_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Raydium
+    pub serum_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub serum_market: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub serum_bids: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub serum_asks: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Raydium
+    pub serum_event_queue: UncheckedAccount<'info>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub serum_coin_vault_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub serum_pc_vault_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked by Raydium
+    pub serum_vault_signer: UncheckedAccount<'info>,
+
+    pub amm_program: Program<'info, RaydiumAmmProgram>,
+
+    /// CHECK: Must match the vault's oracle, required only if the vault has one
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct DripOrcaWhirlpoolTwoHopAccounts<'info> {
+    // swap_token_a_account and swap_token_b_account are the token vaults of whirlpool_one
+    pub common: DripCommonAccounts<'info>,
+
+    // mut reason: CPI
+    #[account(mut)]
+    pub whirlpool_one: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_one_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_one_1: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_one_2: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Whirlpool
+    pub oracle_one: UncheckedAccount<'info>,
+
+    // Vault owned token account for the intermediate token
+    // mut reason: changing balance
+    #[account(mut)]
+    pub intermediate_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mut reason: CPI
+    #[account(mut)]
+    pub whirlpool_two: Box<Account<'info, Whirlpool>>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub whirlpool_two_token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mut reason: changing balance
+    #[account(mut)]
+    pub whirlpool_two_token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_two_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_two_1: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Whirlpool
+    pub tick_array_two_2: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Whirlpool
+    pub oracle_two: UncheckedAccount<'info>,
+
+    pub whirlpool_program: Program<'info, WhirlpoolProgram>,
+
+    /// CHECK: Must match the vault's oracle, required only if the vault has one
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct DripSplitAccounts<'info> {
+    // swap_token_a_account and swap_token_b_account are not used, each leg has its own
+    pub common: DripCommonAccounts<'info>,
+
+    /// CHECK: Must match the vault's oracle, required only if the vault has one.
+    /// Passed before the legs' remaining accounts
+    pub price_oracle: Option<UncheckedAccount<'info>>,
 }
@@ -1,4 +1,4 @@
-use crate::state::{Vault, VaultPeriod, VaultProtoConfig};
+use crate::state::{KeeperRewardType, Vault, VaultPeriod, VaultProtoConfig};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -8,6 +8,10 @@ pub struct InitializeVaultProtoConfigParams {
     pub token_b_withdrawal_spread: u16,
     pub token_b_referral_spread: u16,
     pub admin: Pubkey,
+    // If true, granularity must divide a day evenly
+    pub calendar_aligned: bool,
+    pub keeper_reward_type: KeeperRewardType,
+    pub keeper_reward_lamports: u64,
 }
 
 #[derive(Accounts)]
@@ -37,7 +41,7 @@ pub struct InitializeVaultPeriodParams {
 pub struct InitializeVaultPeriodAccounts<'info> {
     #[account(
         init,
-        space = VaultPeriod::ACCOUNT_SPACE,
+        space = VaultPeriod::ACCOUNT_SPACE + VaultPeriod::RESERVED_SPACE,
         seeds = [
             b"vault_period".as_ref(),
             vault.key().as_ref(),
@@ -54,3 +58,19 @@ pub struct InitializeVaultPeriodAccounts<'info> {
     pub creator: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct ReallocVaultPeriodAccounts<'info> {
+    // mut needed because we are debiting SOL from the signer to pay for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Vault periods created before the latest fields were added can't be deserialized
+    // mut reason: growing the account
+    #[account(mut, owner = crate::ID)]
+    pub vault_period: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
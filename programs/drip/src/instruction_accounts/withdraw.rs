@@ -1,9 +1,9 @@
 use crate::errors::DripError;
-use crate::state::{Position, Vault, VaultPeriod, VaultProtoConfig};
+use crate::state::{Position, Referrer, Vault, VaultPeriod, VaultProtoConfig};
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::Mint;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct WithdrawCommonAccounts<'info> {
@@ -38,26 +38,42 @@ pub struct WithdrawCommonAccounts<'info> {
     /* TOKEN ACCOUNTS */
     // mut needed for close_position
     #[account(mut)]
-    pub user_position_nft_account: Account<'info, TokenAccount>,
+    pub user_position_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     // mut needed because we are changing the balance
     #[account(mut)]
-    pub vault_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut needed because we are changing balance
     #[account(mut)]
-    pub vault_treasury_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub vault_treasury_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut needed because we are changing the balance
     #[account(mut)]
-    pub user_token_b_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut needed because we are changing the balance
+    // Token b ATA of the position's referrer wallet, the referral cut goes to the treasury
+    // while it doesn't exist
     #[account(mut)]
-    pub referrer: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in validate_common, deserialized in execute_withdraw_b if it exists
+    pub referrer: UncheckedAccount<'info>,
+
+    /* MINTS */
+    // Needed for transfer_checked
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /* MISC */
-    pub token_program: Program<'info, Token>,
+    // Token program of the position NFT
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    // Only needed if the position's referrer has a custom referral spread
+    // Optional accounts are positional, so this is kept last for existing clients
+    // mut needed because we are updating the referral stats
+    #[account(mut)]
+    pub referrer_account: Option<Box<Account<'info, Referrer>>>,
 }
 
 #[derive(Accounts)]
@@ -65,6 +81,39 @@ pub struct WithdrawBAccounts<'info> {
     pub common: WithdrawCommonAccounts<'info>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DecreasePositionParams {
+    // Swaps remaining in the position after the decrease
+    pub number_of_swaps: u64,
+    pub periodic_drip_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct DecreasePositionAccounts<'info> {
+    // Accrued token B is withdrawn before the position is decreased
+    pub common: WithdrawCommonAccounts<'info>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault_period_user_expiry: Box<Account<'info, VaultPeriod>>,
+
+    // mut needed because we are changing state
+    #[account(mut)]
+    pub vault_period_new_expiry: Box<Account<'info, VaultPeriod>>,
+
+    // mut needed because we are changing balance
+    #[account(mut)]
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // mut needed because we are changing balance
+    #[account(mut)]
+    pub user_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_a_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct ClosePositionAccounts<'info> {
     pub common: WithdrawCommonAccounts<'info>,
@@ -75,15 +124,19 @@ pub struct ClosePositionAccounts<'info> {
 
     // mut needed because we are changing balance
     #[account(mut)]
-    pub vault_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // mut needed because we are changing balance
     #[account(mut)]
-    pub user_token_a_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_a_program: Interface<'info, TokenInterface>,
 
     // mut needed because we are burning the users NFT
     #[account(mut)]
-    pub user_position_nft_mint: Box<Account<'info, Mint>>,
+    pub user_position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(mut)]
     /// CHECK: validated in validate
@@ -3,15 +3,15 @@ use std::fmt;
 use crate::sign;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint};
-use anchor_spl::token::{Burn, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Mint};
+use anchor_spl::token_interface::{Burn, TokenAccount, TokenInterface};
 
 use super::executor::CpiIdentifier;
 
 pub struct BurnToken<'info> {
-    token_program: Program<'info, Token>,
-    mint: Account<'info, Mint>,
-    from: Account<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
+    mint: InterfaceAccount<'info, Mint>,
+    from: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     authority: AccountInfo<'info>,
     amount: u64,
@@ -19,9 +19,9 @@ pub struct BurnToken<'info> {
 
 impl<'info> BurnToken<'info> {
     pub fn new(
-        token_program: &Program<'info, Token>,
-        mint: &Account<'info, Mint>,
-        from: &Account<'info, TokenAccount>,
+        token_program: &Interface<'info, TokenInterface>,
+        mint: &InterfaceAccount<'info, Mint>,
+        from: &InterfaceAccount<'info, TokenAccount>,
         authority: &AccountInfo<'info>,
         amount: u64,
     ) -> Self {
@@ -49,7 +49,7 @@ impl<'info> fmt::Debug for BurnToken<'info> {
 
 impl<'info> CPI for BurnToken<'info> {
     fn execute(&self, signer: &dyn PDA) -> Result<()> {
-        token::burn(
+        token_interface::burn(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 Burn {
@@ -3,14 +3,14 @@ use std::fmt;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::{Token, TokenAccount};
-use spl_token::instruction::close_account;
+use anchor_spl::token_2022::spl_token_2022::instruction::close_account;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
 use super::executor::CpiIdentifier;
 
 pub struct CloseAccount<'info> {
-    token_program: Program<'info, Token>,
-    token_account: Account<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
+    token_account: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     destination: AccountInfo<'info>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
@@ -19,8 +19,8 @@ pub struct CloseAccount<'info> {
 
 impl<'info> CloseAccount<'info> {
     pub fn new(
-        token_program: &Program<'info, Token>,
-        token_account: &Account<'info, TokenAccount>,
+        token_program: &Interface<'info, TokenInterface>,
+        token_account: &InterfaceAccount<'info, TokenAccount>,
         destination: &AccountInfo<'info>,
         authority: &AccountInfo<'info>,
     ) -> Self {
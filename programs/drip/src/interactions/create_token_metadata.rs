@@ -4,7 +4,7 @@ use crate::sign;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 use mpl_token_metadata::instructions::CreateMetadataAccountV3Builder;
 use mpl_token_metadata::types::DataV2;
 
@@ -25,7 +25,7 @@ pub struct CreateTokenMetadata<'info> {
     system_program: Program<'info, System>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     metadata_account: AccountInfo<'info>,
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     authority: AccountInfo<'info>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
@@ -41,7 +41,7 @@ impl<'info> CreateTokenMetadata<'info> {
         metadata_program: &Program<'info, MetaplexTokenMetadata>,
         system_program: &Program<'info, System>,
         metadata_account: &AccountInfo<'info>,
-        mint: &Account<'info, Mint>,
+        mint: &InterfaceAccount<'info, Mint>,
         authority: &AccountInfo<'info>,
         payer: &AccountInfo<'info>,
         rent: &Sysvar<'info, Rent>,
@@ -19,6 +19,8 @@ impl CpiExecutor for RealCpiExecutor {
     }
 }
 
+// Only used to identify cpis in tests, so the variant size difference doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq)]
 pub enum CpiIdentifier {
     MintToken {
@@ -30,11 +32,18 @@ pub enum CpiIdentifier {
     },
     TransferToken {
         token_program: Pubkey,
+        mint: Pubkey,
         from: Pubkey,
         to: Pubkey,
         authority: Pubkey,
         amount: u64,
     },
+    TransferLamports {
+        system_program: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+        lamports: u64,
+    },
     BurnToken {
         token_program: Pubkey,
         mint: Pubkey,
@@ -60,6 +69,10 @@ pub enum CpiIdentifier {
         destination: Pubkey,
         authority: Pubkey,
     },
+    SyncNative {
+        token_program: Pubkey,
+        account: Pubkey,
+    },
     SetMintAuthority {
         token_program: Pubkey,
         mint: Pubkey,
@@ -83,6 +96,41 @@ pub enum CpiIdentifier {
         sqrt_price_limit: u128,
         a_to_b: bool,
     },
+    SwapAggregator {
+        router_program: Pubkey,
+        token_authority: Pubkey,
+        route_accounts: Vec<Pubkey>,
+        data: Vec<u8>,
+    },
+    SwapRaydium {
+        amm_program: Pubkey,
+        token_program: Pubkey,
+        amm: Pubkey,
+        amm_authority: Pubkey,
+        amm_open_orders: Pubkey,
+        amm_target_orders: Pubkey,
+        pool_coin_token_account: Pubkey,
+        pool_pc_token_account: Pubkey,
+        serum_program: Pubkey,
+        serum_market: Pubkey,
+        serum_bids: Pubkey,
+        serum_asks: Pubkey,
+        serum_event_queue: Pubkey,
+        serum_coin_vault_account: Pubkey,
+        serum_pc_vault_account: Pubkey,
+        serum_vault_signer: Pubkey,
+        user_source_token_account: Pubkey,
+        user_destination_token_account: Pubkey,
+        user_owner: Pubkey,
+        amount_in: u64,
+        minimum_out: u64,
+    },
+    SwapTwoHop {
+        // amount_in of the second leg is the intermediate balance after the first leg
+        leg_one: Box<CpiIdentifier>,
+        leg_two: Box<CpiIdentifier>,
+        intermediate_token_account: Pubkey,
+    },
     SwapSPLTokenSwap {
         token_swap_program: Pubkey,
         token_program: Pubkey,
@@ -3,15 +3,15 @@ use std::fmt;
 use crate::sign;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint};
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{self, Mint};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
 use super::executor::CpiIdentifier;
 
 pub struct MintToken<'info> {
-    token_program: Program<'info, Token>,
-    mint: Account<'info, Mint>,
-    to: Account<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
+    mint: InterfaceAccount<'info, Mint>,
+    to: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     authority: AccountInfo<'info>,
     amount: u64,
@@ -19,9 +19,9 @@ pub struct MintToken<'info> {
 
 impl<'info> MintToken<'info> {
     pub fn new(
-        token_program: &Program<'info, Token>,
-        mint: &Account<'info, Mint>,
-        to: &Account<'info, TokenAccount>,
+        token_program: &Interface<'info, TokenInterface>,
+        mint: &InterfaceAccount<'info, Mint>,
+        to: &InterfaceAccount<'info, TokenAccount>,
         authority: &AccountInfo<'info>,
         amount: u64,
     ) -> Self {
@@ -49,10 +49,10 @@ impl<'info> fmt::Debug for MintToken<'info> {
 
 impl<'info> CPI for MintToken<'info> {
     fn execute(&self, signer: &dyn PDA) -> Result<()> {
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                token::MintTo {
+                token_interface::MintTo {
                     mint: self.mint.to_account_info(),
                     to: self.to.to_account_info(),
                     authority: self.authority.clone(),
@@ -4,6 +4,11 @@ pub mod create_token_metadata;
 pub mod executor;
 pub mod mint_token;
 pub mod set_mint_authority;
+pub mod swap_aggregator;
 pub mod swap_orca_whirlpool;
+pub mod swap_raydium;
 pub mod swap_spl_token_swap;
+pub mod swap_two_hop;
+pub mod sync_native;
+pub mod transfer_lamports;
 pub mod transfer_token;
@@ -4,15 +4,16 @@ use crate::sign;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::Mint;
-use anchor_spl::token::Token;
-use spl_token::instruction::AuthorityType;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::TokenInterface;
 
 use super::executor::CpiIdentifier;
 
 pub struct SetMintAuthority<'info> {
-    token_program: Program<'info, Token>,
-    mint: Account<'info, Mint>,
+    token_program: Interface<'info, TokenInterface>,
+    mint: InterfaceAccount<'info, Mint>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     current_authority: AccountInfo<'info>,
     new_authority: Option<AccountInfo<'info>>,
@@ -20,8 +21,8 @@ pub struct SetMintAuthority<'info> {
 
 impl<'info> SetMintAuthority<'info> {
     pub fn new(
-        token_program: &Program<'info, Token>,
-        mint: &Account<'info, Mint>,
+        token_program: &Interface<'info, TokenInterface>,
+        mint: &InterfaceAccount<'info, Mint>,
         current_authority: &AccountInfo<'info>,
         new_authority: Option<&AccountInfo<'info>>,
     ) -> Self {
@@ -48,7 +49,7 @@ impl<'info> fmt::Debug for SetMintAuthority<'info> {
 impl<'info> CPI for SetMintAuthority<'info> {
     fn execute(&self, signer: &dyn PDA) -> Result<()> {
         invoke_signed(
-            &spl_token::instruction::set_authority(
+            &spl_token_2022::instruction::set_authority(
                 self.token_program.key,
                 &self.mint.key(),
                 self.new_authority.clone().map(|acc| acc.key),
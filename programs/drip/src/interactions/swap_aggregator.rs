@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::sign;
+use crate::state::traits::{CPI, PDA};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use super::executor::CpiIdentifier;
+
+pub struct SwapAggregator<'info> {
+    /// CHECK: whitelisted on the vault
+    router_program: AccountInfo<'info>,
+    /// CHECK: not needed here
+    token_authority: AccountInfo<'info>,
+    route_accounts: Vec<AccountInfo<'info>>,
+    data: Vec<u8>,
+}
+
+impl<'info> SwapAggregator<'info> {
+    pub fn new(
+        router_program: &AccountInfo<'info>,
+        token_authority: &AccountInfo<'info>,
+        route_accounts: &[AccountInfo<'info>],
+        data: &[u8],
+    ) -> Self {
+        SwapAggregator {
+            router_program: router_program.clone(),
+            token_authority: token_authority.clone(),
+            route_accounts: route_accounts.to_vec(),
+            data: data.to_vec(),
+        }
+    }
+}
+
+impl<'info> fmt::Debug for SwapAggregator<'info> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwapAggregator")
+            .field("router_program", &self.router_program.key)
+            .field("token_authority", &self.token_authority.key)
+            .field(
+                "route_accounts",
+                &self
+                    .route_accounts
+                    .iter()
+                    .map(|account| account.key)
+                    .collect::<Vec<_>>(),
+            )
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl<'info> CPI for SwapAggregator<'info> {
+    fn execute(&self, signer: &dyn PDA) -> Result<()> {
+        // The vault is the only account we can sign for, every other signer must already have signed the tx
+        let accounts = self
+            .route_accounts
+            .iter()
+            .map(|account| {
+                let is_signer = account.is_signer || account.key() == self.token_authority.key();
+                if account.is_writable {
+                    AccountMeta::new(account.key(), is_signer)
+                } else {
+                    AccountMeta::new_readonly(account.key(), is_signer)
+                }
+            })
+            .collect();
+
+        let ix = Instruction {
+            program_id: self.router_program.key(),
+            accounts,
+            data: self.data.clone(),
+        };
+
+        let mut account_infos = self.route_accounts.clone();
+        account_infos.push(self.token_authority.to_account_info());
+        account_infos.push(self.router_program.to_account_info());
+
+        invoke_signed(&ix, &account_infos, &[sign!(signer)])?;
+
+        Ok(())
+    }
+
+    fn id(&self) -> CpiIdentifier {
+        CpiIdentifier::SwapAggregator {
+            router_program: self.router_program.key(),
+            token_authority: self.token_authority.key(),
+            route_accounts: self
+                .route_accounts
+                .iter()
+                .map(|account| account.key())
+                .collect(),
+            data: self.data.clone(),
+        }
+    }
+}
@@ -6,22 +6,23 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use borsh::BorshSerialize;
 
 use super::executor::CpiIdentifier;
 
+#[derive(Clone)]
 pub struct SwapOrcaWhirlpool<'info> {
     whirlpool_program: Program<'info, WhirlpoolProgram>,
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     /// CHECK: not needed here
     token_authority: AccountInfo<'info>,
     /// CHECK: not needed here
     whirlpool: AccountInfo<'info>,
-    token_owner_account_a: Box<Account<'info, TokenAccount>>,
-    whirlpool_token_vault_a: Box<Account<'info, TokenAccount>>,
-    token_owner_account_b: Box<Account<'info, TokenAccount>>,
-    whirlpool_token_vault_b: Box<Account<'info, TokenAccount>>,
+    token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    whirlpool_token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    whirlpool_token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
     /// CHECK: not needed here
     tick_array_0: AccountInfo<'info>,
     /// CHECK: not needed here
@@ -38,13 +39,13 @@ pub struct SwapOrcaWhirlpool<'info> {
 impl<'info> SwapOrcaWhirlpool<'info> {
     pub fn new(
         whirlpool_program: &Program<'info, WhirlpoolProgram>,
-        token_program: &Program<'info, Token>,
+        token_program: &Interface<'info, TokenInterface>,
         token_authority: &AccountInfo<'info>,
         whirlpool: &AccountInfo<'info>,
-        token_owner_account_a: &Box<Account<'info, TokenAccount>>,
-        token_vault_a: &Box<Account<'info, TokenAccount>>,
-        token_owner_account_b: &Box<Account<'info, TokenAccount>>,
-        token_vault_b: &Box<Account<'info, TokenAccount>>,
+        token_owner_account_a: &Box<InterfaceAccount<'info, TokenAccount>>,
+        token_vault_a: &Box<InterfaceAccount<'info, TokenAccount>>,
+        token_owner_account_b: &Box<InterfaceAccount<'info, TokenAccount>>,
+        token_vault_b: &Box<InterfaceAccount<'info, TokenAccount>>,
         tick_array_0: &AccountInfo<'info>,
         tick_array_1: &AccountInfo<'info>,
         tick_array_2: &AccountInfo<'info>,
@@ -76,6 +77,13 @@ impl<'info> SwapOrcaWhirlpool<'info> {
             a_to_b,
         }
     }
+
+    pub fn with_amount_in(&self, amount_in: u64) -> Self {
+        SwapOrcaWhirlpool {
+            amount_in,
+            ..self.clone()
+        }
+    }
 }
 
 impl<'info> fmt::Debug for SwapOrcaWhirlpool<'info> {
@@ -0,0 +1,577 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::errors::DripError;
+use crate::state::traits::{CPI, PDA};
+use crate::{sign, validate, RaydiumAmmProgram};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use borsh::BorshSerialize;
+
+use super::executor::CpiIdentifier;
+
+// Instruction tag of swap_base_in in the Raydium AMM program
+const SWAP_BASE_IN_INSTRUCTION: u8 = 9;
+
+// Offsets of the swap fee into a Raydium AMM (v4) AmmInfo account
+const SWAP_FEE_NUMERATOR_OFFSET: usize = 176;
+const SWAP_FEE_DENOMINATOR_OFFSET: usize = 184;
+// Offsets of the pnl the AMM hasn't taken out of its vaults yet, into the same AmmInfo account
+const NEED_TAKE_PNL_COIN_OFFSET: usize = 192;
+const NEED_TAKE_PNL_PC_OFFSET: usize = 200;
+
+// Offsets of the AMM's funds on the order book into a Serum OpenOrders account
+const OPEN_ORDERS_NATIVE_COIN_TOTAL_OFFSET: usize = 85;
+const OPEN_ORDERS_NATIVE_PC_TOTAL_OFFSET: usize = 101;
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    validate!(data.len() >= offset + 8, DripError::InvalidSwapAccount);
+    let mut bytes = [0_u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    Ok(u64::from_le_bytes(bytes))
+}
+
+// The pool vaults are passed in (coin, pc) order, coin being the serum market's base mint
+fn is_token_a_coin(
+    swap_token_a_account: &InterfaceAccount<TokenAccount>,
+    serum_coin_vault_account: &InterfaceAccount<TokenAccount>,
+) -> bool {
+    swap_token_a_account.mint == serum_coin_vault_account.mint
+}
+
+// Amount that reaches the pool's curve once the AMM's swap fee (rounded up like Raydium) is taken
+pub fn get_raydium_amount_in_after_fees(amm: &AccountInfo, amount_in: u64) -> Result<u64> {
+    let data = amm.try_borrow_data()?;
+    let swap_fee_numerator = u128::from(read_u64(&data, SWAP_FEE_NUMERATOR_OFFSET)?);
+    let swap_fee_denominator = u128::from(read_u64(&data, SWAP_FEE_DENOMINATOR_OFFSET)?);
+    validate!(
+        swap_fee_numerator < swap_fee_denominator,
+        DripError::InvalidSwapAccount
+    );
+
+    let amount_in = u128::from(amount_in);
+    let swap_fee = amount_in
+        .checked_mul(swap_fee_numerator)
+        .unwrap()
+        .checked_add(swap_fee_denominator - 1)
+        .unwrap()
+        / swap_fee_denominator;
+    Ok(u64::try_from(amount_in - swap_fee).unwrap())
+}
+
+// Reserves Raydium prices swaps against: the pool vaults plus the AMM's funds on the order book,
+// less the pnl it hasn't taken yet. Raydium rejects open orders that aren't the AMM's, so the
+// swap can't run against reserves read from a different account
+// Returns (token a reserve, token b reserve)
+pub fn get_raydium_reserves(
+    amm: &AccountInfo,
+    amm_open_orders: &AccountInfo,
+    swap_token_a_account: &InterfaceAccount<TokenAccount>,
+    swap_token_b_account: &InterfaceAccount<TokenAccount>,
+    serum_coin_vault_account: &InterfaceAccount<TokenAccount>,
+) -> Result<(u64, u64)> {
+    let amm_data = amm.try_borrow_data()?;
+    let open_orders_data = amm_open_orders.try_borrow_data()?;
+    let need_take_pnl_coin = read_u64(&amm_data, NEED_TAKE_PNL_COIN_OFFSET)?;
+    let need_take_pnl_pc = read_u64(&amm_data, NEED_TAKE_PNL_PC_OFFSET)?;
+    let a_is_coin = is_token_a_coin(swap_token_a_account, serum_coin_vault_account);
+    let (coin_vault_amount, pc_vault_amount) = if a_is_coin {
+        (swap_token_a_account.amount, swap_token_b_account.amount)
+    } else {
+        (swap_token_b_account.amount, swap_token_a_account.amount)
+    };
+
+    let coin_reserve = coin_vault_amount
+        .checked_add(read_u64(
+            &open_orders_data,
+            OPEN_ORDERS_NATIVE_COIN_TOTAL_OFFSET,
+        )?)
+        .and_then(|total| total.checked_sub(need_take_pnl_coin))
+        .ok_or(DripError::InvalidSwapAccount)?;
+    let pc_reserve = pc_vault_amount
+        .checked_add(read_u64(
+            &open_orders_data,
+            OPEN_ORDERS_NATIVE_PC_TOTAL_OFFSET,
+        )?)
+        .and_then(|total| total.checked_sub(need_take_pnl_pc))
+        .ok_or(DripError::InvalidSwapAccount)?;
+
+    Ok(if a_is_coin {
+        (coin_reserve, pc_reserve)
+    } else {
+        (pc_reserve, coin_reserve)
+    })
+}
+
+// Accounts of a Raydium AMM (v4) swap_base_in, with the pool vaults in the vault's (a, b) order
+pub struct SwapRaydiumAccounts<'a, 'info> {
+    pub amm_program: &'a Program<'info, RaydiumAmmProgram>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub amm: &'a AccountInfo<'info>,
+    pub amm_authority: &'a AccountInfo<'info>,
+    pub amm_open_orders: &'a AccountInfo<'info>,
+    pub amm_target_orders: &'a AccountInfo<'info>,
+    pub swap_token_a_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub swap_token_b_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub serum_program: &'a AccountInfo<'info>,
+    pub serum_market: &'a AccountInfo<'info>,
+    pub serum_bids: &'a AccountInfo<'info>,
+    pub serum_asks: &'a AccountInfo<'info>,
+    pub serum_event_queue: &'a AccountInfo<'info>,
+    pub serum_coin_vault_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub serum_pc_vault_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub serum_vault_signer: &'a AccountInfo<'info>,
+    pub user_source_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_destination_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_owner: &'a AccountInfo<'info>,
+}
+
+pub struct SwapRaydium<'info> {
+    amm_program: Program<'info, RaydiumAmmProgram>,
+    token_program: Interface<'info, TokenInterface>,
+    /// CHECK: not needed here
+    amm: AccountInfo<'info>,
+    /// CHECK: not needed here
+    amm_authority: AccountInfo<'info>,
+    /// CHECK: not needed here
+    amm_open_orders: AccountInfo<'info>,
+    /// CHECK: not needed here
+    amm_target_orders: AccountInfo<'info>,
+    pool_coin_token_account: InterfaceAccount<'info, TokenAccount>,
+    pool_pc_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: not needed here
+    serum_program: AccountInfo<'info>,
+    /// CHECK: not needed here
+    serum_market: AccountInfo<'info>,
+    /// CHECK: not needed here
+    serum_bids: AccountInfo<'info>,
+    /// CHECK: not needed here
+    serum_asks: AccountInfo<'info>,
+    /// CHECK: not needed here
+    serum_event_queue: AccountInfo<'info>,
+    serum_coin_vault_account: InterfaceAccount<'info, TokenAccount>,
+    serum_pc_vault_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: not needed here
+    serum_vault_signer: AccountInfo<'info>,
+    user_source_token_account: InterfaceAccount<'info, TokenAccount>,
+    user_destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: not needed here
+    user_owner: AccountInfo<'info>,
+    amount_in: u64,
+    minimum_out: u64,
+}
+
+impl<'info> SwapRaydium<'info> {
+    pub fn new(accounts: SwapRaydiumAccounts<'_, 'info>, amount_in: u64, minimum_out: u64) -> Self {
+        let (pool_coin_token_account, pool_pc_token_account) = if is_token_a_coin(
+            accounts.swap_token_a_account,
+            accounts.serum_coin_vault_account,
+        ) {
+            (accounts.swap_token_a_account, accounts.swap_token_b_account)
+        } else {
+            (accounts.swap_token_b_account, accounts.swap_token_a_account)
+        };
+        SwapRaydium {
+            amm_program: accounts.amm_program.clone(),
+            token_program: accounts.token_program.clone(),
+            amm: accounts.amm.clone(),
+            amm_authority: accounts.amm_authority.clone(),
+            amm_open_orders: accounts.amm_open_orders.clone(),
+            amm_target_orders: accounts.amm_target_orders.clone(),
+            pool_coin_token_account: pool_coin_token_account.clone(),
+            pool_pc_token_account: pool_pc_token_account.clone(),
+            serum_program: accounts.serum_program.clone(),
+            serum_market: accounts.serum_market.clone(),
+            serum_bids: accounts.serum_bids.clone(),
+            serum_asks: accounts.serum_asks.clone(),
+            serum_event_queue: accounts.serum_event_queue.clone(),
+            serum_coin_vault_account: accounts.serum_coin_vault_account.clone(),
+            serum_pc_vault_account: accounts.serum_pc_vault_account.clone(),
+            serum_vault_signer: accounts.serum_vault_signer.clone(),
+            user_source_token_account: accounts.user_source_token_account.clone(),
+            user_destination_token_account: accounts.user_destination_token_account.clone(),
+            user_owner: accounts.user_owner.clone(),
+            amount_in,
+            minimum_out,
+        }
+    }
+}
+
+impl<'info> fmt::Debug for SwapRaydium<'info> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwapRaydium")
+            .field("amm_program", &self.amm_program.key)
+            .field("token_program", &self.token_program.key)
+            .field("amm", &self.amm)
+            .field("amm_authority", &self.amm_authority)
+            .field("amm_open_orders", &self.amm_open_orders)
+            .field("amm_target_orders", &self.amm_target_orders)
+            .field("pool_coin_token_account", &self.pool_coin_token_account)
+            .field("pool_pc_token_account", &self.pool_pc_token_account)
+            .field("serum_program", &self.serum_program)
+            .field("serum_market", &self.serum_market)
+            .field("serum_bids", &self.serum_bids)
+            .field("serum_asks", &self.serum_asks)
+            .field("serum_event_queue", &self.serum_event_queue)
+            .field("serum_coin_vault_account", &self.serum_coin_vault_account)
+            .field("serum_pc_vault_account", &self.serum_pc_vault_account)
+            .field("serum_vault_signer", &self.serum_vault_signer)
+            .field("user_source_token_account", &self.user_source_token_account)
+            .field(
+                "user_destination_token_account",
+                &self.user_destination_token_account,
+            )
+            .field("user_owner", &self.user_owner)
+            .field("amount_in", &self.amount_in)
+            .field("minimum_out", &self.minimum_out)
+            .finish()
+    }
+}
+
+#[derive(BorshSerialize)]
+struct RaydiumSwapBaseInParams {
+    instruction: u8,
+    amount_in: u64,
+    minimum_amount_out: u64,
+}
+
+impl<'info> CPI for SwapRaydium<'info> {
+    fn execute(&self, signer: &dyn PDA) -> Result<()> {
+        let params = RaydiumSwapBaseInParams {
+            instruction: SWAP_BASE_IN_INSTRUCTION,
+            amount_in: self.amount_in,
+            minimum_amount_out: self.minimum_out,
+        };
+        let mut buffer: Vec<u8> = Vec::new();
+        params.serialize(&mut buffer).unwrap();
+
+        let ix = Instruction {
+            program_id: self.amm_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(self.token_program.key(), false),
+                AccountMeta::new(self.amm.key(), false),
+                AccountMeta::new_readonly(self.amm_authority.key(), false),
+                AccountMeta::new(self.amm_open_orders.key(), false),
+                AccountMeta::new(self.amm_target_orders.key(), false),
+                AccountMeta::new(self.pool_coin_token_account.key(), false),
+                AccountMeta::new(self.pool_pc_token_account.key(), false),
+                AccountMeta::new_readonly(self.serum_program.key(), false),
+                AccountMeta::new(self.serum_market.key(), false),
+                AccountMeta::new(self.serum_bids.key(), false),
+                AccountMeta::new(self.serum_asks.key(), false),
+                AccountMeta::new(self.serum_event_queue.key(), false),
+                AccountMeta::new(self.serum_coin_vault_account.key(), false),
+                AccountMeta::new(self.serum_pc_vault_account.key(), false),
+                AccountMeta::new_readonly(self.serum_vault_signer.key(), false),
+                AccountMeta::new(self.user_source_token_account.key(), false),
+                AccountMeta::new(self.user_destination_token_account.key(), false),
+                AccountMeta::new_readonly(self.user_owner.key(), true),
+            ],
+            data: buffer,
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                self.token_program.to_account_info(),
+                self.amm.to_account_info(),
+                self.amm_authority.to_account_info(),
+                self.amm_open_orders.to_account_info(),
+                self.amm_target_orders.to_account_info(),
+                self.pool_coin_token_account.to_account_info(),
+                self.pool_pc_token_account.to_account_info(),
+                self.serum_program.to_account_info(),
+                self.serum_market.to_account_info(),
+                self.serum_bids.to_account_info(),
+                self.serum_asks.to_account_info(),
+                self.serum_event_queue.to_account_info(),
+                self.serum_coin_vault_account.to_account_info(),
+                self.serum_pc_vault_account.to_account_info(),
+                self.serum_vault_signer.to_account_info(),
+                self.user_source_token_account.to_account_info(),
+                self.user_destination_token_account.to_account_info(),
+                self.user_owner.to_account_info(),
+            ],
+            &[sign!(signer)],
+        )?;
+
+        Ok(())
+    }
+
+    fn id(&self) -> CpiIdentifier {
+        CpiIdentifier::SwapRaydium {
+            amm_program: self.amm_program.key(),
+            token_program: self.token_program.key(),
+            amm: self.amm.key(),
+            amm_authority: self.amm_authority.key(),
+            amm_open_orders: self.amm_open_orders.key(),
+            amm_target_orders: self.amm_target_orders.key(),
+            pool_coin_token_account: self.pool_coin_token_account.key(),
+            pool_pc_token_account: self.pool_pc_token_account.key(),
+            serum_program: self.serum_program.key(),
+            serum_market: self.serum_market.key(),
+            serum_bids: self.serum_bids.key(),
+            serum_asks: self.serum_asks.key(),
+            serum_event_queue: self.serum_event_queue.key(),
+            serum_coin_vault_account: self.serum_coin_vault_account.key(),
+            serum_pc_vault_account: self.serum_pc_vault_account.key(),
+            serum_vault_signer: self.serum_vault_signer.key(),
+            user_source_token_account: self.user_source_token_account.key(),
+            user_destination_token_account: self.user_destination_token_account.key(),
+            user_owner: self.user_owner.key(),
+            amount_in: self.amount_in,
+            minimum_out: self.minimum_out,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interactions::executor::test::TestCpiExecutor;
+    use crate::interactions::executor::CpiExecutor;
+    use crate::state::Vault;
+    use crate::test::fixtures::{new_token_account, AccountFixture, NoData, VAULT};
+    use std::convert::TryFrom;
+    use test_case::test_case;
+
+    fn new_pool_token_account(
+        mint: Pubkey,
+        amount: u64,
+    ) -> AccountFixture<anchor_spl::token::TokenAccount> {
+        new_token_account(mint, Pubkey::new_unique(), amount, None)
+    }
+
+    fn new_open_orders(native_coin_total: u64, native_pc_total: u64) -> AccountFixture<NoData> {
+        let mut account = AccountFixture::new_system_account(None);
+        account.data = vec![0; 3228];
+        account.data
+            [OPEN_ORDERS_NATIVE_COIN_TOTAL_OFFSET..OPEN_ORDERS_NATIVE_COIN_TOTAL_OFFSET + 8]
+            .copy_from_slice(&native_coin_total.to_le_bytes());
+        account.data[OPEN_ORDERS_NATIVE_PC_TOTAL_OFFSET..OPEN_ORDERS_NATIVE_PC_TOTAL_OFFSET + 8]
+            .copy_from_slice(&native_pc_total.to_le_bytes());
+
+        account
+    }
+
+    fn new_amm(
+        swap_fee_numerator: u64,
+        swap_fee_denominator: u64,
+        need_take_pnl_coin: u64,
+        need_take_pnl_pc: u64,
+    ) -> AccountFixture<NoData> {
+        let mut account = AccountFixture::new_system_account(None);
+        account.owner = RaydiumAmmProgram::id();
+        account.data = vec![0; 752];
+        for (offset, value) in [
+            (SWAP_FEE_NUMERATOR_OFFSET, swap_fee_numerator),
+            (SWAP_FEE_DENOMINATOR_OFFSET, swap_fee_denominator),
+            (NEED_TAKE_PNL_COIN_OFFSET, need_take_pnl_coin),
+            (NEED_TAKE_PNL_PC_OFFSET, need_take_pnl_pc),
+        ] {
+            account.data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+        }
+
+        account
+    }
+
+    #[test_case(25, 10000, 10000, 9975; "Takes the swap fee")]
+    #[test_case(25, 10000, 100, 99; "Rounds the fee up")]
+    #[test_case(0, 10000, 100, 100; "Works without a fee")]
+    fn get_raydium_amount_in_after_fees_tests(
+        swap_fee_numerator: u64,
+        swap_fee_denominator: u64,
+        amount_in: u64,
+        expected_amount_in: u64,
+    ) {
+        let mut amm = new_amm(swap_fee_numerator, swap_fee_denominator, 0, 0);
+        assert_eq!(
+            get_raydium_amount_in_after_fees(&amm.to_account_info(), amount_in).unwrap(),
+            expected_amount_in
+        );
+    }
+
+    #[test_case(10000, 10000; "Fee of 100%")]
+    #[test_case(0, 0; "Uninitialized fees")]
+    fn get_raydium_amount_in_after_fees_error_tests(
+        swap_fee_numerator: u64,
+        swap_fee_denominator: u64,
+    ) {
+        let mut amm = new_amm(swap_fee_numerator, swap_fee_denominator, 0, 0);
+        assert!(get_raydium_amount_in_after_fees(&amm.to_account_info(), 100).is_err());
+    }
+
+    #[test_case(true, 0, 0, 0, 0, (1000, 2000); "Uses the pool vaults")]
+    #[test_case(true, 100, 200, 0, 0, (1100, 2200); "Adds the funds on the order book")]
+    #[test_case(true, 100, 200, 50, 150, (1050, 2050); "Removes the pnl the AMM hasn't taken")]
+    #[test_case(false, 100, 200, 50, 150, (1050, 2050); "Orders the reserves when token a is the pc")]
+    fn get_raydium_reserves_tests(
+        a_is_coin: bool,
+        native_token_a_total: u64,
+        native_token_b_total: u64,
+        need_take_pnl_a: u64,
+        need_take_pnl_b: u64,
+        expected: (u64, u64),
+    ) {
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let (coin_mint, native_coin_total, native_pc_total, need_take_pnl_coin, need_take_pnl_pc) =
+            if a_is_coin {
+                (
+                    mint_a,
+                    native_token_a_total,
+                    native_token_b_total,
+                    need_take_pnl_a,
+                    need_take_pnl_b,
+                )
+            } else {
+                (
+                    mint_b,
+                    native_token_b_total,
+                    native_token_a_total,
+                    need_take_pnl_b,
+                    need_take_pnl_a,
+                )
+            };
+
+        let mut amm = new_amm(25, 10000, need_take_pnl_coin, need_take_pnl_pc);
+        let mut amm_open_orders = new_open_orders(native_coin_total, native_pc_total);
+        let mut swap_token_a_account = new_pool_token_account(mint_a, 1000);
+        let mut swap_token_b_account = new_pool_token_account(mint_b, 2000);
+        let mut serum_coin_vault_account = new_pool_token_account(coin_mint, 0);
+
+        let swap_token_a_account_info = &swap_token_a_account.to_account_info();
+        let swap_token_a_account = InterfaceAccount::try_from(swap_token_a_account_info).unwrap();
+        let swap_token_b_account_info = &swap_token_b_account.to_account_info();
+        let swap_token_b_account = InterfaceAccount::try_from(swap_token_b_account_info).unwrap();
+        let serum_coin_vault_account_info = &serum_coin_vault_account.to_account_info();
+        let serum_coin_vault_account =
+            InterfaceAccount::try_from(serum_coin_vault_account_info).unwrap();
+
+        assert_eq!(
+            get_raydium_reserves(
+                &amm.to_account_info(),
+                &amm_open_orders.to_account_info(),
+                &swap_token_a_account,
+                &swap_token_b_account,
+                &serum_coin_vault_account,
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test_case(true; "Token a is the coin")]
+    #[test_case(false; "Token a is the pc")]
+    fn swap_raydium_orders_pool_accounts(a_is_coin: bool) {
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let (coin_mint, pc_mint) = if a_is_coin {
+            (mint_a, mint_b)
+        } else {
+            (mint_b, mint_a)
+        };
+
+        let mut amm_program = AccountFixture::<NoData>::new_program(RaydiumAmmProgram::id());
+        let mut token_program = AccountFixture::<NoData>::new_program(anchor_spl::token::ID);
+        let mut unchecked = AccountFixture::<NoData>::new_system_account(None);
+        let mut swap_token_a_account = new_pool_token_account(mint_a, 0);
+        let mut swap_token_b_account = new_pool_token_account(mint_b, 0);
+        let mut serum_coin_vault_account = new_pool_token_account(coin_mint, 0);
+        let mut serum_pc_vault_account = new_pool_token_account(pc_mint, 0);
+        let mut vault_token_a_account = new_pool_token_account(mint_a, 0);
+        let mut vault_token_b_account = new_pool_token_account(mint_b, 0);
+        let mut vault = VAULT.clone();
+
+        let amm_program_account_info = &amm_program.to_account_info();
+        let amm_program = Program::try_from(amm_program_account_info).unwrap();
+        let token_program_account_info = &token_program.to_account_info();
+        let token_program = Interface::try_from(token_program_account_info).unwrap();
+        let unchecked = &unchecked.to_account_info();
+        let swap_token_a_account_info = &swap_token_a_account.to_account_info();
+        let swap_token_a_account = InterfaceAccount::try_from(swap_token_a_account_info).unwrap();
+        let swap_token_b_account_info = &swap_token_b_account.to_account_info();
+        let swap_token_b_account = InterfaceAccount::try_from(swap_token_b_account_info).unwrap();
+        let serum_coin_vault_account_info = &serum_coin_vault_account.to_account_info();
+        let serum_coin_vault_account =
+            InterfaceAccount::try_from(serum_coin_vault_account_info).unwrap();
+        let serum_pc_vault_account_info = &serum_pc_vault_account.to_account_info();
+        let serum_pc_vault_account =
+            InterfaceAccount::try_from(serum_pc_vault_account_info).unwrap();
+        let vault_token_a_account_info = &vault_token_a_account.to_account_info();
+        let vault_token_a_account = InterfaceAccount::try_from(vault_token_a_account_info).unwrap();
+        let vault_token_b_account_info = &vault_token_b_account.to_account_info();
+        let vault_token_b_account = InterfaceAccount::try_from(vault_token_b_account_info).unwrap();
+        let vault_account_info = &vault.to_account_info();
+
+        let swap = SwapRaydium::new(
+            SwapRaydiumAccounts {
+                amm_program: &amm_program,
+                token_program: &token_program,
+                amm: unchecked,
+                amm_authority: unchecked,
+                amm_open_orders: unchecked,
+                amm_target_orders: unchecked,
+                swap_token_a_account: &swap_token_a_account,
+                swap_token_b_account: &swap_token_b_account,
+                serum_program: unchecked,
+                serum_market: unchecked,
+                serum_bids: unchecked,
+                serum_asks: unchecked,
+                serum_event_queue: unchecked,
+                serum_coin_vault_account: &serum_coin_vault_account,
+                serum_pc_vault_account: &serum_pc_vault_account,
+                serum_vault_signer: unchecked,
+                user_source_token_account: &vault_token_a_account,
+                user_destination_token_account: &vault_token_b_account,
+                user_owner: vault_account_info,
+            },
+            100,
+            1,
+        );
+
+        let mut signer: Vault = VAULT.account.clone();
+        signer.bump = Pubkey::find_program_address(&signer.seeds(), &crate::ID).1;
+
+        let mut cpi_executor = TestCpiExecutor {
+            cpi_calls: vec![],
+            signer: None,
+        };
+        let res = cpi_executor.execute_all(vec![&Some(&swap)], &signer);
+        assert_eq!(res, Ok(()));
+
+        let (pool_coin_token_account, pool_pc_token_account) = if a_is_coin {
+            (swap_token_a_account.key(), swap_token_b_account.key())
+        } else {
+            (swap_token_b_account.key(), swap_token_a_account.key())
+        };
+        assert_eq!(
+            cpi_executor.cpi_calls,
+            vec![CpiIdentifier::SwapRaydium {
+                amm_program: RaydiumAmmProgram::id(),
+                token_program: anchor_spl::token::ID,
+                amm: unchecked.key(),
+                amm_authority: unchecked.key(),
+                amm_open_orders: unchecked.key(),
+                amm_target_orders: unchecked.key(),
+                pool_coin_token_account,
+                pool_pc_token_account,
+                serum_program: unchecked.key(),
+                serum_market: unchecked.key(),
+                serum_bids: unchecked.key(),
+                serum_asks: unchecked.key(),
+                serum_event_queue: unchecked.key(),
+                serum_coin_vault_account: serum_coin_vault_account.key(),
+                serum_pc_vault_account: serum_pc_vault_account.key(),
+                serum_vault_signer: unchecked.key(),
+                user_source_token_account: vault_token_a_account.key(),
+                user_destination_token_account: vault_token_b_account.key(),
+                user_owner: vault_account_info.key(),
+                amount_in: 100,
+                minimum_out: 1,
+            }]
+        );
+    }
+}
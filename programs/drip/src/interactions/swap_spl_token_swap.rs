@@ -4,25 +4,25 @@ use crate::state::traits::{CPI, PDA};
 use crate::{sign, TokenSwap};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use super::executor::CpiIdentifier;
 
 pub struct SwapSPLTokenSwap<'info> {
     token_swap_program: Program<'info, TokenSwap>,
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     /// CHECK: not needed here
     token_swap: AccountInfo<'info>,
     /// CHECK: not needed here
     swap_authority: AccountInfo<'info>,
     /// CHECK: not needed here
     user_transfer_authority: AccountInfo<'info>,
-    user_token_a_account: Box<Account<'info, TokenAccount>>,
-    swap_token_a_account: Box<Account<'info, TokenAccount>>,
-    swap_token_b_account: Box<Account<'info, TokenAccount>>,
-    user_token_b_account: Box<Account<'info, TokenAccount>>,
-    swap_mint: Box<Account<'info, Mint>>,
-    swap_fee_account: Box<Account<'info, TokenAccount>>,
+    user_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    swap_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    swap_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    user_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    swap_mint: Box<InterfaceAccount<'info, Mint>>,
+    swap_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
     amount_in: u64,
     minimum_out: u64,
 }
@@ -30,16 +30,16 @@ pub struct SwapSPLTokenSwap<'info> {
 impl<'info> SwapSPLTokenSwap<'info> {
     pub fn new(
         token_swap_program: &Program<'info, TokenSwap>,
-        token_program: &Program<'info, Token>,
+        token_program: &Interface<'info, TokenInterface>,
         token_swap: &AccountInfo<'info>,
         swap_authority: &AccountInfo<'info>,
         user_transfer_authority: &AccountInfo<'info>,
-        user_token_a_account: &Box<Account<'info, TokenAccount>>,
-        swap_token_a_account: &Box<Account<'info, TokenAccount>>,
-        swap_token_b_account: &Box<Account<'info, TokenAccount>>,
-        user_token_b_account: &Box<Account<'info, TokenAccount>>,
-        swap_mint: &Box<Account<'info, Mint>>,
-        swap_fee_account: &Box<Account<'info, TokenAccount>>,
+        user_token_a_account: &Box<InterfaceAccount<'info, TokenAccount>>,
+        swap_token_a_account: &Box<InterfaceAccount<'info, TokenAccount>>,
+        swap_token_b_account: &Box<InterfaceAccount<'info, TokenAccount>>,
+        user_token_b_account: &Box<InterfaceAccount<'info, TokenAccount>>,
+        swap_mint: &Box<InterfaceAccount<'info, Mint>>,
+        swap_fee_account: &Box<InterfaceAccount<'info, TokenAccount>>,
         amount_in: u64,
         minimum_out: u64,
     ) -> Self {
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::errors::DripError;
+use crate::state::traits::{CPI, PDA};
+use crate::validate;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use super::executor::CpiIdentifier;
+use super::swap_orca_whirlpool::SwapOrcaWhirlpool;
+
+pub struct SwapTwoHop<'info> {
+    leg_one: SwapOrcaWhirlpool<'info>,
+    leg_two: SwapOrcaWhirlpool<'info>,
+    intermediate_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> SwapTwoHop<'info> {
+    pub fn new(
+        leg_one: SwapOrcaWhirlpool<'info>,
+        leg_two: SwapOrcaWhirlpool<'info>,
+        intermediate_token_account: &InterfaceAccount<'info, TokenAccount>,
+    ) -> Self {
+        SwapTwoHop {
+            leg_one,
+            leg_two,
+            intermediate_token_account: intermediate_token_account.clone(),
+        }
+    }
+
+    fn intermediate_balance(&self) -> Result<u64> {
+        let account_info = self.intermediate_token_account.to_account_info();
+        let data = account_info.try_borrow_data()?;
+        Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+    }
+}
+
+impl<'info> fmt::Debug for SwapTwoHop<'info> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwapTwoHop")
+            .field("leg_one", &self.leg_one)
+            .field("leg_two", &self.leg_two)
+            .field(
+                "intermediate_token_account",
+                &self.intermediate_token_account,
+            )
+            .finish()
+    }
+}
+
+impl<'info> CPI for SwapTwoHop<'info> {
+    fn execute(&self, signer: &dyn PDA) -> Result<()> {
+        self.leg_one.execute(signer)?;
+
+        // The whole intermediate balance is swapped, so the account never carries dust between
+        // drips, and the drip fails if the second leg doesn't consume all of it
+        let intermediate_amount = self.intermediate_balance()?;
+        self.leg_two
+            .with_amount_in(intermediate_amount)
+            .execute(signer)?;
+
+        validate!(
+            self.intermediate_balance()? == 0,
+            DripError::IntermediateBalanceNotSwapped
+        );
+
+        Ok(())
+    }
+
+    fn id(&self) -> CpiIdentifier {
+        CpiIdentifier::SwapTwoHop {
+            leg_one: Box::new(self.leg_one.id()),
+            leg_two: Box::new(self.leg_two.id()),
+            intermediate_token_account: self.intermediate_token_account.key(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::state::traits::{CPI, PDA};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::instruction::sync_native;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use super::executor::CpiIdentifier;
+
+pub struct SyncNative<'info> {
+    token_program: Interface<'info, TokenInterface>,
+    account: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> SyncNative<'info> {
+    pub fn new(
+        token_program: &Interface<'info, TokenInterface>,
+        account: &InterfaceAccount<'info, TokenAccount>,
+    ) -> Self {
+        SyncNative {
+            token_program: token_program.clone(),
+            account: account.clone(),
+        }
+    }
+}
+
+impl<'info> fmt::Debug for SyncNative<'info> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncNative")
+            .field("token_program", &self.token_program.key)
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+impl<'info> CPI for SyncNative<'info> {
+    fn execute(&self, _: &dyn PDA) -> Result<()> {
+        invoke(
+            &sync_native(self.token_program.key, &self.account.key())?,
+            &[
+                self.token_program.to_account_info(),
+                self.account.to_account_info(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn id(&self) -> CpiIdentifier {
+        CpiIdentifier::SyncNative {
+            token_program: self.token_program.key(),
+            account: self.account.key(),
+        }
+    }
+}
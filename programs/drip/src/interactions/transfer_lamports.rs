@@ -0,0 +1,67 @@
+use std::fmt;
+
+use crate::state::traits::{CPI, PDA};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction::transfer;
+
+use super::executor::CpiIdentifier;
+
+pub struct TransferLamports<'info> {
+    system_program: Program<'info, System>,
+    /// CHECK: Suppress anchor error, this isn't an IX context struct
+    from: AccountInfo<'info>,
+    /// CHECK: Suppress anchor error, this isn't an IX context struct
+    to: AccountInfo<'info>,
+    lamports: u64,
+}
+
+impl<'info> TransferLamports<'info> {
+    pub fn new(
+        system_program: &Program<'info, System>,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        lamports: u64,
+    ) -> Self {
+        TransferLamports {
+            system_program: system_program.clone(),
+            from: from.clone(),
+            to: to.clone(),
+            lamports,
+        }
+    }
+}
+
+impl<'info> fmt::Debug for TransferLamports<'info> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransferLamports")
+            .field("system_program", &self.system_program.key)
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("lamports", &self.lamports)
+            .finish()
+    }
+}
+
+impl<'info> CPI for TransferLamports<'info> {
+    fn execute(&self, _: &dyn PDA) -> Result<()> {
+        invoke(
+            &transfer(self.from.key, self.to.key, self.lamports),
+            &[
+                self.from.to_account_info(),
+                self.to.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn id(&self) -> CpiIdentifier {
+        CpiIdentifier::TransferLamports {
+            system_program: self.system_program.key(),
+            from: self.from.key(),
+            to: self.to.key(),
+            lamports: self.lamports,
+        }
+    }
+}
@@ -3,14 +3,16 @@ use std::fmt;
 use crate::sign;
 use crate::state::traits::{CPI, PDA};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 use super::executor::CpiIdentifier;
 
 pub struct TransferToken<'info> {
-    token_program: Program<'info, Token>,
-    from: Account<'info, TokenAccount>,
-    to: Account<'info, TokenAccount>,
+    token_program: Interface<'info, TokenInterface>,
+    mint: InterfaceAccount<'info, Mint>,
+    from: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Suppress anchor error, this isn't an IX context struct
+    to: AccountInfo<'info>,
     /// CHECK: Suppress anchor error, this isn't an IX context struct
     authority: AccountInfo<'info>,
     amount: u64,
@@ -18,14 +20,16 @@ pub struct TransferToken<'info> {
 
 impl<'info> TransferToken<'info> {
     pub fn new(
-        token_program: &Program<'info, Token>,
-        from: &Account<'info, TokenAccount>,
-        to: &Account<'info, TokenAccount>,
+        token_program: &Interface<'info, TokenInterface>,
+        mint: &InterfaceAccount<'info, Mint>,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        to: &AccountInfo<'info>,
         authority: &AccountInfo<'info>,
         amount: u64,
     ) -> Self {
         TransferToken {
             token_program: token_program.clone(),
+            mint: mint.clone(),
             from: from.clone(),
             to: to.clone(),
             authority: authority.clone(),
@@ -38,6 +42,7 @@ impl<'info> fmt::Debug for TransferToken<'info> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TransferToken")
             .field("token_program", &self.token_program.key)
+            .field("mint", &self.mint)
             .field("from", &self.from)
             .field("to", &self.to)
             .field("authority", &self.authority)
@@ -48,23 +53,27 @@ impl<'info> fmt::Debug for TransferToken<'info> {
 
 impl<'info> CPI for TransferToken<'info> {
     fn execute(&self, signer: &dyn PDA) -> Result<()> {
-        anchor_spl::token::transfer(
+        // transfer_checked is required by token-2022 mints with a transfer fee
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                anchor_spl::token::Transfer {
+                token_interface::TransferChecked {
                     from: self.from.to_account_info(),
-                    to: self.to.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.to.clone(),
                     authority: self.authority.clone(),
                 },
                 &[sign!(signer)],
             ),
             self.amount,
+            self.mint.decimals,
         )
     }
 
     fn id(&self) -> CpiIdentifier {
         CpiIdentifier::TransferToken {
             token_program: self.token_program.key(),
+            mint: self.mint.key(),
             from: self.from.key(),
             to: self.to.key(),
             authority: self.authority.key(),
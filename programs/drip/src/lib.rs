@@ -50,6 +50,25 @@ pub mod drip {
         })
     }
 
+    pub fn deposit_sol(ctx: Context<DepositSolAccounts>, params: DepositParams) -> Result<()> {
+        handle_action(Deposit::Sol {
+            accounts: ctx.accounts,
+            params,
+            bumps: ctx.bumps,
+        })
+    }
+
+    pub fn deposit_sol_with_metadata(
+        ctx: Context<DepositSolWithMetadataAccounts>,
+        params: DepositParams,
+    ) -> Result<()> {
+        handle_action(Deposit::SolWithMetadata {
+            accounts: ctx.accounts,
+            params,
+            bumps: ctx.bumps,
+        })
+    }
+
     pub fn deposit_with_metadata(
         ctx: Context<DepositWithMetadataAccounts>,
         params: DepositParams,
@@ -61,6 +80,16 @@ pub mod drip {
         })
     }
 
+    pub fn increase_position(
+        ctx: Context<IncreasePositionAccounts>,
+        params: IncreasePositionParams,
+    ) -> Result<()> {
+        handle_action(Deposit::IncreasePosition {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
     pub fn drip_spl_token_swap(ctx: Context<DripSPLTokenSwapAccounts>) -> Result<()> {
         handle_action(Drip::SPLTokenSwap {
             accounts: ctx.accounts,
@@ -73,6 +102,60 @@ pub mod drip {
         })
     }
 
+    pub fn drip_orca_whirlpool_catch_up<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DripOrcaWhirlpoolAccounts<'info>>,
+    ) -> Result<()> {
+        handle_action(Drip::OrcaWhirlpoolCatchUp {
+            accounts: ctx.accounts,
+            remaining_accounts: ctx.remaining_accounts,
+        })
+    }
+
+    pub fn check_vault_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckVaultInvariantsAccounts<'info>>,
+    ) -> Result<()> {
+        handle_action(Check::VaultInvariants {
+            accounts: ctx.accounts,
+            remaining_accounts: ctx.remaining_accounts,
+        })
+    }
+
+    pub fn drip_orca_whirlpool_two_hop(
+        ctx: Context<DripOrcaWhirlpoolTwoHopAccounts>,
+    ) -> Result<()> {
+        handle_action(Drip::OrcaWhirlpoolTwoHop {
+            accounts: ctx.accounts,
+        })
+    }
+
+    pub fn drip_raydium(ctx: Context<DripRaydiumAccounts>) -> Result<()> {
+        handle_action(Drip::Raydium {
+            accounts: ctx.accounts,
+        })
+    }
+
+    pub fn drip_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DripSplitAccounts<'info>>,
+        params: DripSplitParams,
+    ) -> Result<()> {
+        handle_action(Drip::Split {
+            accounts: ctx.accounts,
+            remaining_accounts: ctx.remaining_accounts,
+            params,
+        })
+    }
+
+    pub fn drip_aggregator<'info>(
+        ctx: Context<'_, '_, '_, 'info, DripAggregatorAccounts<'info>>,
+        params: DripAggregatorParams,
+    ) -> Result<()> {
+        handle_action(Drip::Aggregator {
+            accounts: ctx.accounts,
+            remaining_accounts: ctx.remaining_accounts,
+            params,
+        })
+    }
+
     pub fn withdraw_b(ctx: Context<WithdrawBAccounts>) -> Result<()> {
         handle_action(Withdraw::WithoutClosePosition {
             accounts: ctx.accounts,
@@ -82,11 +165,35 @@ pub mod drip {
     pub fn close_position(ctx: Context<ClosePositionAccounts>) -> Result<()> {
         handle_action(Withdraw::WithClosePosition {
             accounts: ctx.accounts,
+            unwrap_sol: false,
+        })
+    }
+
+    pub fn close_position_and_unwrap_sol(ctx: Context<ClosePositionAccounts>) -> Result<()> {
+        handle_action(Withdraw::WithClosePosition {
+            accounts: ctx.accounts,
+            unwrap_sol: true,
+        })
+    }
+
+    pub fn decrease_position(
+        ctx: Context<DecreasePositionAccounts>,
+        params: DecreasePositionParams,
+    ) -> Result<()> {
+        handle_action(Withdraw::DecreasePosition {
+            accounts: ctx.accounts,
+            params,
         })
     }
 
     // Admin Ix's
 
+    pub fn realloc_vault_period(ctx: Context<ReallocVaultPeriodAccounts>) -> Result<()> {
+        handle_action(Init::ReallocVaultPeriod {
+            accounts: ctx.accounts,
+        })
+    }
+
     pub fn init_vault(
         ctx: Context<InitializeVaultAccounts>,
         params: InitializeVaultParams,
@@ -108,6 +215,72 @@ pub mod drip {
         })
     }
 
+    pub fn set_vault_keeper_whitelist(
+        ctx: Context<SetVaultKeeperWhitelistAccounts>,
+        params: SetVaultKeeperWhitelistParams,
+    ) -> Result<()> {
+        handle_action(Admin::SetVaultKeeperWhitelist {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn set_vault_drip_schedule(
+        ctx: Context<SetVaultDripScheduleAccounts>,
+        params: SetVaultDripScheduleParams,
+    ) -> Result<()> {
+        handle_action(Admin::SetVaultDripSchedule {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn set_vault_paused(
+        ctx: Context<SetVaultPausedAccounts>,
+        params: SetVaultPausedParams,
+    ) -> Result<()> {
+        handle_action(Admin::SetVaultPaused {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn set_vault_oracle(
+        ctx: Context<SetVaultOracleAccounts>,
+        params: SetVaultOracleParams,
+    ) -> Result<()> {
+        handle_action(Admin::SetVaultOracle {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdminAccounts>,
+        params: ProposeAdminParams,
+    ) -> Result<()> {
+        handle_action(Admin::ProposeAdmin {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdminAccounts>) -> Result<()> {
+        handle_action(Admin::AcceptAdmin {
+            accounts: ctx.accounts,
+        })
+    }
+
+    pub fn update_vault_proto_config_spreads(
+        ctx: Context<UpdateVaultProtoConfigSpreadsAccounts>,
+        params: UpdateVaultProtoConfigSpreadsParams,
+    ) -> Result<()> {
+        handle_action(Admin::UpdateVaultProtoConfigSpreads {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
     /*
        DEPRECATED: USE admin_withdraw
     */
@@ -123,6 +296,44 @@ pub mod drip {
         })
     }
 
+    // Also callable by the position's referrer wallet
+    pub fn update_referrer(
+        ctx: Context<UpdateReferrerAccounts>,
+        params: UpdateReferrerParams,
+    ) -> Result<()> {
+        handle_action(Admin::UpdateReferrer {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn init_referrer(
+        ctx: Context<InitializeReferrerAccounts>,
+        params: InitializeReferrerParams,
+    ) -> Result<()> {
+        handle_action(Admin::InitReferrer {
+            accounts: ctx.accounts,
+            params,
+            bumps: ctx.bumps,
+        })
+    }
+
+    pub fn update_referrer_spread(
+        ctx: Context<UpdateReferrerSpreadAccounts>,
+        params: UpdateReferrerSpreadParams,
+    ) -> Result<()> {
+        handle_action(Admin::UpdateReferrerSpread {
+            accounts: ctx.accounts,
+            params,
+        })
+    }
+
+    pub fn close_referrer(ctx: Context<CloseReferrerAccounts>) -> Result<()> {
+        handle_action(Admin::CloseReferrer {
+            accounts: ctx.accounts,
+        })
+    }
+
     pub fn admin_close_position_account(ctx: Context<ClosePositionAccountAccounts>) -> Result<()> {
         handle_action(Admin::ClosePositionAccount {
             accounts: ctx.accounts,
@@ -1,3 +1,6 @@
+use crate::errors::DripError;
+use crate::validate;
+use anchor_lang::prelude::Result;
 use std::{convert::TryFrom, u128};
 
 fn calculate_slippage_factor(max_slippage_bps: u16, a_to_b: bool) -> f64 {
@@ -37,6 +40,148 @@ pub fn calculate_sqrt_price_limit(
         .expect("new sqrt price calc failed 2")
 }
 
+///
+/// # Arguments
+///
+/// * `amount_in`: amount of asset a sent into the swap
+/// * `price_x64`: expected price of asset a expressed in asset b
+/// * `max_slippage_bps`: slippage tolerated from the expected price
+///
+/// returns: u64, or an error if the expected output doesn't fit
+pub fn calculate_minimum_out(
+    amount_in: u64,
+    price_x64: u128,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let minimum_out = u128::from(amount_in)
+        .checked_mul(price_x64)
+        .map(|expected_out_x64| expected_out_x64 >> 64)
+        .and_then(|expected_out| {
+            expected_out.checked_mul(10000_u128.checked_sub(max_slippage_bps.into())?)
+        })
+        .map(|minimum_out| minimum_out / 10000)
+        .and_then(|minimum_out| u64::try_from(minimum_out).ok());
+    minimum_out.ok_or_else(|| DripError::InvalidMinimumOut.into())
+}
+
+///
+/// # Arguments
+///
+/// * `amount_in`: amount of asset a sent into the swap, after the pool's fees
+/// * `reserve_in`: pool reserve of asset a
+/// * `reserve_out`: pool reserve of asset b
+/// * `max_slippage_bps`: slippage tolerated from the pool's spot price
+///
+/// returns: u64, minimum out of a constant product swap, or an error if the pool is empty
+pub fn calculate_constant_product_minimum_out(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    validate!(
+        reserve_in > 0 && reserve_out > 0,
+        DripError::EmptySwapReserves
+    );
+    let minimum_out = calculate_minimum_out(
+        amount_in,
+        compute_price(reserve_out, reserve_in),
+        max_slippage_bps,
+    )?;
+    Ok(minimum_out.max(1))
+}
+
+// Whirlpool fee rates are in hundredths of a basis point
+const WHIRLPOOL_FEE_RATE_DENOMINATOR: u128 = 1_000_000;
+
+// Squares a Q64.64 sqrt price, dropping the low bits of large prices to stay within a u128
+fn calculate_price_from_sqrt_price(sqrt_price_x64: u128) -> Option<u128> {
+    if sqrt_price_x64 <= u128::from(u64::MAX) {
+        Some(sqrt_price_x64.checked_mul(sqrt_price_x64)? >> 64)
+    } else {
+        (sqrt_price_x64 >> 32).checked_mul(sqrt_price_x64 >> 32)
+    }
+}
+
+///
+/// # Arguments
+///
+/// * `amount_in`: amount of the input token sent into the swap
+/// * `sqrt_price_x64`: whirlpool sqrt price of its token a in its token b
+/// * `fee_rate`: whirlpool fee rate in hundredths of a basis point
+/// * `a_to_b`: true if the input token is the whirlpool's token a
+/// * `max_slippage_bps`: slippage tolerated from the whirlpool's spot price
+///
+/// returns: u64, output at the whirlpool's spot price after fees less the slippage
+pub fn calculate_whirlpool_minimum_out(
+    amount_in: u64,
+    sqrt_price_x64: u128,
+    fee_rate: u16,
+    a_to_b: bool,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let amount_in = u128::from(amount_in);
+    let fee_amount = amount_in
+        .checked_mul(fee_rate.into())
+        .and_then(|fee| fee.checked_add(WHIRLPOOL_FEE_RATE_DENOMINATOR - 1))
+        .map(|fee| fee / WHIRLPOOL_FEE_RATE_DENOMINATOR);
+    let expected_out = fee_amount
+        .and_then(|fee_amount| amount_in.checked_sub(fee_amount))
+        .zip(calculate_price_from_sqrt_price(sqrt_price_x64))
+        .and_then(|(amount_in, price_x64)| {
+            if a_to_b {
+                amount_in
+                    .checked_mul(price_x64)
+                    .map(|out_x64| out_x64 >> 64)
+            } else {
+                (amount_in << 64).checked_div(price_x64)
+            }
+        })
+        .and_then(|expected_out| u64::try_from(expected_out).ok())
+        .ok_or(DripError::InvalidMinimumOut)?;
+
+    calculate_minimum_out(expected_out, 1 << 64, max_slippage_bps)
+}
+
+///
+/// # Arguments
+///
+/// * `price`, `expo`: oracle price of one whole token a in whole token b (price * 10^expo)
+/// * `token_a_decimals`, `token_b_decimals`: mint decimals used to convert to base units
+///
+/// returns: price of a base unit of asset a expressed in base units of asset b, in the same
+/// x64 format as `compute_price`, or None if it doesn't fit
+pub fn calculate_oracle_price_x64(
+    price: u64,
+    expo: i32,
+    token_a_decimals: u8,
+    token_b_decimals: u8,
+) -> Option<u128> {
+    let exponent = i64::from(expo)
+        .checked_add(i64::from(token_b_decimals))?
+        .checked_sub(i64::from(token_a_decimals))?;
+    let scale = 10_u128.checked_pow(u32::try_from(exponent.checked_abs()?).ok()?)?;
+    let price_x64 = u128::from(price).checked_shl(64)?;
+    if exponent >= 0 {
+        price_x64.checked_mul(scale)
+    } else {
+        price_x64.checked_div(scale)
+    }
+}
+
+pub fn is_price_within_deviation(
+    price_x64: u128,
+    reference_price_x64: u128,
+    max_deviation_bps: u16,
+) -> bool {
+    let max_deviation = reference_price_x64
+        .checked_div(10000)
+        .unwrap()
+        .checked_mul(max_deviation_bps.into())
+        .unwrap();
+    price_x64.abs_diff(reference_price_x64) <= max_deviation
+}
+
 pub fn calculate_periodic_drip_amount(total_amount: u64, number_of_swaps: u64) -> u64 {
     total_amount.checked_div(number_of_swaps).unwrap()
 }
@@ -67,6 +212,19 @@ pub fn calculate_withdraw_token_a_amount(
     remaining_swaps.checked_mul(periodic_drip_amount).unwrap()
 }
 
+///
+/// # Arguments
+///
+/// * `i`: the last completed period before deposit (drip_period_id_before_deposit)
+/// * `j`: the min of vault.last_drip_period, and user position expiry (drip_period_id_before_deposit + number_of_swaps)
+/// * `user_position_number_of_swaps`: total number of swaps the user will participate in
+///
+/// returns: u64
+pub fn calculate_remaining_swaps(i: u64, j: u64, user_position_number_of_swaps: u64) -> u64 {
+    let swaps_completed_since_user_deposit = j.checked_sub(i).unwrap();
+    user_position_number_of_swaps.saturating_sub(swaps_completed_since_user_deposit)
+}
+
 ///
 /// # Arguments
 ///
@@ -136,6 +294,56 @@ pub fn calculate_new_twap_amount(twap_i_minus_1: u128, i: u64, price_i: u128) ->
         .unwrap()
 }
 
+///
+/// # Arguments
+///
+/// * `b_per_a_cumulative_i_x64`: b_per_a_cumulative of the last completed period before deposit
+/// * `b_per_a_cumulative_j_x64`: b_per_a_cumulative of the min of vault.last_drip_period and user position expiry
+/// * `periodic_drip_amount`: amount of asset a used in each period to buy asset b
+///
+/// returns: u64
+pub fn calculate_withdraw_token_b_amount_from_b_per_a(
+    b_per_a_cumulative_i_x64: u128,
+    b_per_a_cumulative_j_x64: u128,
+    periodic_drip_amount: u64,
+) -> u64 {
+    let amount_x64 = b_per_a_cumulative_j_x64
+        .checked_sub(b_per_a_cumulative_i_x64)
+        .unwrap()
+        .checked_mul(u128::from(periodic_drip_amount))
+        .unwrap();
+
+    u64::try_from(amount_x64.checked_shr(64).unwrap()).unwrap()
+}
+
+// Wrapping so that the cumulative can grow forever, differences are still correct after a wrap
+pub fn calculate_time_weighted_price_cumulative(
+    previous_cumulative_x64: u128,
+    price_x64: u128,
+    elapsed_seconds: u64,
+) -> u128 {
+    previous_cumulative_x64.wrapping_add(price_x64.wrapping_mul(u128::from(elapsed_seconds)))
+}
+
+///
+/// # Arguments
+///
+/// * `cumulative_i_x64`: time_weighted_price_cumulative of the earlier period
+/// * `cumulative_j_x64`: time_weighted_price_cumulative of the later period
+/// * `elapsed_seconds`: seconds between the drips of the two periods
+///
+/// returns: u128, average price of asset A in asset B weighted by time, 0 if no time elapsed
+pub fn calculate_time_weighted_average_price(
+    cumulative_i_x64: u128,
+    cumulative_j_x64: u128,
+    elapsed_seconds: u64,
+) -> u128 {
+    cumulative_j_x64
+        .wrapping_sub(cumulative_i_x64)
+        .checked_div(u128::from(elapsed_seconds))
+        .unwrap_or(0)
+}
+
 pub fn compute_price(token_b_amount: u64, token_a_amount: u64) -> u128 {
     let numerator_x64 = u128::from(token_b_amount).checked_shl(64).unwrap();
     let denominator = u128::from(token_a_amount);
@@ -177,6 +385,84 @@ pub fn calculate_drip_activation_timestamp(
     }
 }
 
+// Drip periods start on granularity boundaries, so the offset into the current period is the
+// time since activation modulo the granularity
+pub fn is_within_drip_execution_window(
+    current_time: i64,
+    drip_activation_timestamp: i64,
+    granularity: u64,
+    drip_execution_window: u64,
+) -> bool {
+    if drip_execution_window == 0 {
+        return true;
+    }
+    let elapsed =
+        u64::try_from(current_time.saturating_sub(drip_activation_timestamp)).unwrap_or(0);
+    elapsed.checked_rem(granularity).unwrap() < drip_execution_window
+}
+
+// What the vault owes to a set of its positions
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct VaultObligations {
+    // Sum of the periodic drip amounts of the positions that still have swaps remaining
+    pub drip_amount: u64,
+    // Token A that has not been dripped yet
+    pub token_a_amount: u64,
+    // Token B that has been dripped but not withdrawn yet, including the withdrawal spreads
+    pub token_b_amount: u64,
+}
+
+impl VaultObligations {
+    pub fn add_position(
+        &mut self,
+        remaining_swaps: u64,
+        periodic_drip_amount: u64,
+        withdrawable_token_b_amount: u64,
+    ) {
+        if remaining_swaps > 0 {
+            self.drip_amount = self.drip_amount.checked_add(periodic_drip_amount).unwrap();
+        }
+        self.token_a_amount = self
+            .token_a_amount
+            .checked_add(remaining_swaps.checked_mul(periodic_drip_amount).unwrap())
+            .unwrap();
+        self.token_b_amount = self
+            .token_b_amount
+            .checked_add(withdrawable_token_b_amount)
+            .unwrap();
+    }
+}
+
+///
+/// # Arguments
+///
+/// * `vault_token_a_balance`: balance of the vault token a account
+/// * `vault_token_b_balance`: balance of the vault token b account
+/// * `vault_drip_amount`: token a dripped by the vault in the next period
+/// * `obligations`: what the vault owes to the positions being checked, these can be a subset of all positions
+///
+/// returns: Ok if the vault balances cover the vault drip amount and the obligations
+pub fn check_vault_invariants(
+    vault_token_a_balance: u64,
+    vault_token_b_balance: u64,
+    vault_drip_amount: u64,
+    obligations: &VaultObligations,
+) -> Result<()> {
+    validate!(
+        obligations.drip_amount <= vault_drip_amount,
+        DripError::PositionsExceedVaultDripAmount
+    );
+    validate!(
+        vault_token_a_balance >= vault_drip_amount.max(obligations.token_a_amount),
+        DripError::InsufficientVaultTokenABalance
+    );
+    validate!(
+        vault_token_b_balance >= obligations.token_b_amount,
+        DripError::InsufficientVaultTokenBBalance
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -202,6 +488,93 @@ mod test {
         );
     }
 
+    #[test_case(0, 3 << 64, 4, 12; "Can withdraw B from the first period")]
+    #[test_case(10 << 64, 25 << 64, 4, 60; "Can withdraw B when not starting from first period")]
+    #[test_case(10 << 64, 10 << 64, 4, 0; "Can withdraw 0 B when i equals j")]
+    #[test_case(0, (1 << 64) / 3, 3, 0; "Rounds down")]
+    fn calculate_withdraw_token_b_amount_from_b_per_a_tests(
+        b_per_a_cumulative_i_x64: u128,
+        b_per_a_cumulative_j_x64: u128,
+        periodic_drip_amount: u64,
+        expected: u64,
+    ) {
+        assert_eq!(
+            calculate_withdraw_token_b_amount_from_b_per_a(
+                b_per_a_cumulative_i_x64,
+                b_per_a_cumulative_j_x64,
+                periodic_drip_amount
+            ),
+            expected
+        );
+    }
+
+    #[test_case(0, 1 << 64, 60, 60 << 64; "Weights the price by the elapsed seconds")]
+    #[test_case(100, 1 << 64, 0, 100; "Ignores prices without elapsed time")]
+    #[test_case(u128::MAX, 2, 1, 1; "Wraps on overflow")]
+    fn calculate_time_weighted_price_cumulative_tests(
+        previous_cumulative_x64: u128,
+        price_x64: u128,
+        elapsed_seconds: u64,
+        expected: u128,
+    ) {
+        assert_eq!(
+            calculate_time_weighted_price_cumulative(
+                previous_cumulative_x64,
+                price_x64,
+                elapsed_seconds
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn calculate_time_weighted_average_price_tests() {
+        // price 1 for 60 seconds followed by price 4 for 180 seconds averages to 3.25
+        let cumulative_1 = calculate_time_weighted_price_cumulative(0, 1 << 64, 60);
+        let cumulative_2 = calculate_time_weighted_price_cumulative(cumulative_1, 4 << 64, 180);
+        assert_eq!(
+            calculate_time_weighted_average_price(0, cumulative_2, 240),
+            (13 << 64) / 4
+        );
+        assert_eq!(
+            calculate_time_weighted_average_price(cumulative_1, cumulative_2, 180),
+            4 << 64
+        );
+        // The average is still correct when the cumulative wrapped in between
+        let start = u128::MAX - 10;
+        let end = calculate_time_weighted_price_cumulative(start, 4 << 64, 180);
+        assert_eq!(
+            calculate_time_weighted_average_price(start, end, 180),
+            4 << 64
+        );
+        assert_eq!(calculate_time_weighted_average_price(0, 0, 0), 0);
+    }
+
+    #[test_case(1661126400, 1661126400, 3600, 0, true; "Returns true without a window")]
+    #[test_case(1661126400 + 3599, 1661126400, 3600, 0, true; "Returns true late in the period without a window")]
+    #[test_case(1661126400, 1661126400, 3600, 300, true; "Returns true at activation")]
+    #[test_case(1661126400 + 299, 1661126400, 3600, 300, true; "Returns true at the end of the window")]
+    #[test_case(1661126400 + 300, 1661126400, 3600, 300, false; "Returns false after the window")]
+    #[test_case(1661126400 + 3600 + 10, 1661126400, 3600, 300, true; "Returns true in the window of a later period")]
+    #[test_case(1661126400 + 7200 + 300, 1661126400, 3600, 300, false; "Returns false after the window of a later period")]
+    fn is_within_drip_execution_window_tests(
+        current_time: i64,
+        drip_activation_timestamp: i64,
+        granularity: u64,
+        drip_execution_window: u64,
+        expected: bool,
+    ) {
+        assert_eq!(
+            is_within_drip_execution_window(
+                current_time,
+                drip_activation_timestamp,
+                granularity,
+                drip_execution_window
+            ),
+            expected
+        );
+    }
+
     #[test_case(1661120813, 0, true)]
     #[test_case(1661120813, 0, false)]
     #[should_panic]
@@ -222,6 +595,134 @@ mod test {
         );
     }
 
+    #[test_case(100, 0, 1000, 0)]
+    #[test_case(100, 1 << 64, 0, 100)]
+    #[test_case(100, 1 << 64, 1000, 90)]
+    #[test_case(1_000_000, 1 << 63, 50, 497_500)]
+    #[test_case(100, 1 << 64, 10000, 0)]
+    fn calculate_minimum_out_tests(
+        amount_in: u64,
+        price_x64: u128,
+        max_slippage_bps: u16,
+        expected_minimum_out: u64,
+    ) {
+        assert_eq!(
+            calculate_minimum_out(amount_in, price_x64, max_slippage_bps).unwrap(),
+            expected_minimum_out
+        );
+    }
+
+    #[test_case(u64::MAX, u128::MAX, 0; "Expected output overflows")]
+    #[test_case(u64::MAX, u128::from(u64::MAX) << 64, 0; "Minimum out doesn't fit a u64")]
+    #[test_case(100, 1 << 64, 10001; "Slippage above 100%")]
+    fn calculate_minimum_out_error_tests(amount_in: u64, price_x64: u128, max_slippage_bps: u16) {
+        assert!(calculate_minimum_out(amount_in, price_x64, max_slippage_bps).is_err());
+    }
+
+    #[test_case(1000, 1000, 2000, 0, 2000; "Spot price of the reserves")]
+    #[test_case(1000, 1000, 2000, 100, 1980; "Removes the slippage")]
+    #[test_case(1, 1000, 1, 0, 1; "Is at least 1")]
+    fn calculate_constant_product_minimum_out_tests(
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        max_slippage_bps: u16,
+        expected_minimum_out: u64,
+    ) {
+        assert_eq!(
+            calculate_constant_product_minimum_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                max_slippage_bps
+            )
+            .unwrap(),
+            expected_minimum_out
+        );
+    }
+
+    #[test_case(1000, 0, 2000; "Empty input reserve")]
+    #[test_case(1000, 1000, 0; "Empty output reserve")]
+    fn calculate_constant_product_minimum_out_error_tests(
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+    ) {
+        assert!(
+            calculate_constant_product_minimum_out(amount_in, reserve_in, reserve_out, 0).is_err()
+        );
+    }
+
+    #[test_case(1000, 1 << 64, 0, true, 0, 1000; "Price of 1")]
+    #[test_case(1000, 2 << 64, 0, true, 0, 4000; "Squares the sqrt price from a to b")]
+    #[test_case(1000, 2 << 64, 0, false, 0, 250; "Inverts the price from b to a")]
+    #[test_case(1000, 1 << 64, 3000, true, 0, 997; "Takes the fee")]
+    #[test_case(1000, 1 << 64, 0, true, 100, 990; "Removes the slippage")]
+    fn calculate_whirlpool_minimum_out_tests(
+        amount_in: u64,
+        sqrt_price_x64: u128,
+        fee_rate: u16,
+        a_to_b: bool,
+        max_slippage_bps: u16,
+        expected_minimum_out: u64,
+    ) {
+        assert_eq!(
+            calculate_whirlpool_minimum_out(
+                amount_in,
+                sqrt_price_x64,
+                fee_rate,
+                a_to_b,
+                max_slippage_bps
+            )
+            .unwrap(),
+            expected_minimum_out
+        );
+    }
+
+    #[test_case(1000, 1 << 96, true; "Output doesn't fit")]
+    #[test_case(1000, 0, false; "Zero price")]
+    fn calculate_whirlpool_minimum_out_error_tests(
+        amount_in: u64,
+        sqrt_price_x64: u128,
+        a_to_b: bool,
+    ) {
+        assert!(calculate_whirlpool_minimum_out(amount_in, sqrt_price_x64, 0, a_to_b, 0).is_err());
+    }
+
+    #[test_case(150, 0, 0, 0, Some(150 << 64); "Same decimals")]
+    #[test_case(15_000_000_000, -8, 9, 6, Some((150 << 64) / 1000); "SOL priced in USDC")]
+    #[test_case(15, -1, 6, 9, Some(1500 << 64); "Token b has more decimals")]
+    #[test_case(u64::MAX, 10, 0, 0, None; "Overflow")]
+    fn calculate_oracle_price_x64_tests(
+        price: u64,
+        expo: i32,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+        expected_price_x64: Option<u128>,
+    ) {
+        assert_eq!(
+            calculate_oracle_price_x64(price, expo, token_a_decimals, token_b_decimals),
+            expected_price_x64
+        );
+    }
+
+    #[test_case(10_000, 10_000, 0, true)]
+    #[test_case(10_100, 10_000, 100, true)]
+    #[test_case(9_900, 10_000, 100, true)]
+    #[test_case(10_101, 10_000, 100, false)]
+    #[test_case(9_899, 10_000, 100, false)]
+    fn is_price_within_deviation_tests(
+        price_x64: u128,
+        reference_price_x64: u128,
+        max_deviation_bps: u16,
+        expected: bool,
+    ) {
+        assert_eq!(
+            is_price_within_deviation(price_x64, reference_price_x64, max_deviation_bps),
+            expected
+        );
+    }
+
     #[test_case(1, 0)]
     #[should_panic]
     fn compute_price_panic_tests(token_b_amount: u64, token_a_amount: u64) {
@@ -300,6 +801,40 @@ mod test {
         );
     }
 
+    #[test_case(2, 2, 8, 8; "All swaps remain right after deposit")]
+    #[test_case(2, 6, 8, 4; "Some swaps remain in the middle of a position")]
+    #[test_case(2, 10, 8, 0; "No swaps remain at the end of a position")]
+    #[test_case(2, 11, 8, 0; "No swaps remain past the end of a position")]
+    fn calculate_remaining_swaps_tests(
+        drip_period_id_before_deposit: u64,
+        current_period: u64,
+        number_of_swaps: u64,
+        expected_remaining_swaps: u64,
+    ) {
+        assert_eq!(
+            calculate_remaining_swaps(
+                drip_period_id_before_deposit,
+                current_period,
+                number_of_swaps
+            ),
+            expected_remaining_swaps
+        );
+    }
+
+    #[test_case(10, 2, 8; "Should panic when current_period < drip_period_id_before_deposit")]
+    #[should_panic]
+    fn calculate_remaining_swaps_panic_tests(
+        drip_period_id_before_deposit: u64,
+        current_period: u64,
+        number_of_swaps: u64,
+    ) {
+        calculate_remaining_swaps(
+            drip_period_id_before_deposit,
+            current_period,
+            number_of_swaps,
+        );
+    }
+
     #[test_case(10, 2, 8, 5; "Can't withdraw when current_period < drip_period_id_before_deposit")]
     #[should_panic]
     fn calculate_withdraw_token_a_amount_panic_tests(
@@ -382,4 +917,51 @@ mod test {
     fn calculate_spread_amount_panic_tests(amount: u64, spread: u16) {
         calculate_spread_amount(amount, spread);
     }
+
+    #[test_case(3, 10, 5, VaultObligations { drip_amount: 10, token_a_amount: 30, token_b_amount: 5 }; "Adds an active position")]
+    #[test_case(0, 10, 5, VaultObligations { drip_amount: 0, token_a_amount: 0, token_b_amount: 5 }; "Does not add the drip amount of a completed position")]
+    fn vault_obligations_add_position_tests(
+        remaining_swaps: u64,
+        periodic_drip_amount: u64,
+        withdrawable_token_b_amount: u64,
+        expected: VaultObligations,
+    ) {
+        let mut obligations = VaultObligations::default();
+        obligations.add_position(
+            remaining_swaps,
+            periodic_drip_amount,
+            withdrawable_token_b_amount,
+        );
+        assert_eq!(obligations, expected);
+    }
+
+    #[test_case(30, 5, 10, 10, 30, 5, Ok(()); "Passes when balances cover the obligations exactly")]
+    #[test_case(10, 0, 10, 0, 0, 0, Ok(()); "Passes without positions")]
+    #[test_case(9, 0, 10, 0, 0, 0, Err(DripError::InsufficientVaultTokenABalance.into()); "Fails if token a does not cover the next drip")]
+    #[test_case(29, 5, 10, 10, 30, 5, Err(DripError::InsufficientVaultTokenABalance.into()); "Fails if token a does not cover the remaining drips")]
+    #[test_case(30, 4, 10, 10, 30, 5, Err(DripError::InsufficientVaultTokenBBalance.into()); "Fails if token b does not cover the withdrawable amounts")]
+    #[test_case(30, 5, 10, 11, 30, 5, Err(DripError::PositionsExceedVaultDripAmount.into()); "Fails if positions drip more than the vault")]
+    fn check_vault_invariants_tests(
+        vault_token_a_balance: u64,
+        vault_token_b_balance: u64,
+        vault_drip_amount: u64,
+        positions_drip_amount: u64,
+        positions_token_a_amount: u64,
+        positions_token_b_amount: u64,
+        expected: Result<()>,
+    ) {
+        assert_eq!(
+            check_vault_invariants(
+                vault_token_a_balance,
+                vault_token_b_balance,
+                vault_drip_amount,
+                &VaultObligations {
+                    drip_amount: positions_drip_amount,
+                    token_a_amount: positions_token_a_amount,
+                    token_b_amount: positions_token_b_amount,
+                },
+            ),
+            expected
+        );
+    }
 }
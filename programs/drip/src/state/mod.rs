@@ -1,11 +1,17 @@
+pub mod oracle;
 pub mod position;
+pub mod referrer;
 pub mod traits;
+pub mod transfer_fee;
 pub mod vault;
 pub mod vault_period;
 pub mod vault_proto_config;
 
+pub use oracle::*;
 pub use position::*;
+pub use referrer::*;
 pub use traits::ByteSized;
+pub use transfer_fee::*;
 pub use vault::*;
 pub use vault_period::*;
 pub use vault_proto_config::*;
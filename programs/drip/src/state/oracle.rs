@@ -0,0 +1,137 @@
+use crate::errors::DripError;
+use crate::validate;
+use anchor_lang::prelude::*;
+use std::convert::TryInto;
+
+// Oracle prices older than this are rejected
+pub const MAX_ORACLE_PRICE_AGE_SECONDS: i64 = 60;
+pub const MAX_ORACLE_DEVIATION_UPPER_LIMIT_EXCLUSIVE: u16 = 10_000;
+
+// Offsets into a pyth (v2) price account
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_MIN_SIZE: usize = 240;
+
+// Aggregate price read from an oracle price account, the price is price * 10^expo
+#[derive(Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    pub fn load(oracle: &AccountInfo) -> Result<OraclePrice> {
+        OraclePrice::unpack(&oracle.try_borrow_data()?)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<OraclePrice> {
+        validate!(
+            data.len() >= PRICE_ACCOUNT_MIN_SIZE
+                && read_u32(data, MAGIC_OFFSET) == PYTH_MAGIC
+                && read_u32(data, VERSION_OFFSET) == PYTH_VERSION
+                && read_u32(data, ACCOUNT_TYPE_OFFSET) == PYTH_PRICE_ACCOUNT_TYPE,
+            DripError::InvalidOracleAccount
+        );
+        validate!(
+            read_u32(data, AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+            DripError::InvalidOraclePrice
+        );
+
+        let price = i64::from_le_bytes(read_bytes(data, AGG_PRICE_OFFSET));
+        validate!(price > 0, DripError::InvalidOraclePrice);
+
+        Ok(OraclePrice {
+            price,
+            expo: i32::from_le_bytes(read_bytes(data, EXPO_OFFSET)),
+            publish_time: i64::from_le_bytes(read_bytes(data, TIMESTAMP_OFFSET)),
+        })
+    }
+
+    pub fn is_stale(&self, now: i64) -> bool {
+        now.saturating_sub(self.publish_time) > MAX_ORACLE_PRICE_AGE_SECONDS
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(read_bytes(data, offset))
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().unwrap()
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use test_case::test_case;
+
+    // Local stand-in for a pyth price account
+    pub fn new_oracle_price_account_data(
+        price: i64,
+        expo: i32,
+        publish_time: i64,
+        status: u32,
+    ) -> Vec<u8> {
+        let mut data = vec![0; PRICE_ACCOUNT_MIN_SIZE];
+        data[MAGIC_OFFSET..MAGIC_OFFSET + 4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[VERSION_OFFSET..VERSION_OFFSET + 4].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[ACCOUNT_TYPE_OFFSET..ACCOUNT_TYPE_OFFSET + 4]
+            .copy_from_slice(&PYTH_PRICE_ACCOUNT_TYPE.to_le_bytes());
+        data[EXPO_OFFSET..EXPO_OFFSET + 4].copy_from_slice(&expo.to_le_bytes());
+        data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].copy_from_slice(&publish_time.to_le_bytes());
+        data[AGG_PRICE_OFFSET..AGG_PRICE_OFFSET + 8].copy_from_slice(&price.to_le_bytes());
+        data[AGG_STATUS_OFFSET..AGG_STATUS_OFFSET + 4].copy_from_slice(&status.to_le_bytes());
+        data
+    }
+
+    #[test_case(150_000_000, -8, 100, PYTH_STATUS_TRADING, Ok(OraclePrice { price: 150_000_000, expo: -8, publish_time: 100 }); "Returns the aggregate price")]
+    #[test_case(150_000_000, -8, 100, 0, Err(DripError::InvalidOraclePrice.into()); "Returns error if the price is not trading")]
+    #[test_case(0, -8, 100, PYTH_STATUS_TRADING, Err(DripError::InvalidOraclePrice.into()); "Returns error for a zero price")]
+    #[test_case(-1, -8, 100, PYTH_STATUS_TRADING, Err(DripError::InvalidOraclePrice.into()); "Returns error for a negative price")]
+    fn unpack_oracle_price_tests(
+        price: i64,
+        expo: i32,
+        publish_time: i64,
+        status: u32,
+        expected_res: Result<OraclePrice>,
+    ) {
+        let data = new_oracle_price_account_data(price, expo, publish_time, status);
+        assert_eq!(OraclePrice::unpack(&data), expected_res);
+    }
+
+    #[test]
+    fn unpack_oracle_price_rejects_other_layouts() {
+        let mut data = new_oracle_price_account_data(1, 0, 0, PYTH_STATUS_TRADING);
+        data[MAGIC_OFFSET] = 0;
+        assert_eq!(
+            OraclePrice::unpack(&data),
+            Err(DripError::InvalidOracleAccount.into())
+        );
+        assert_eq!(
+            OraclePrice::unpack(&data[..PRICE_ACCOUNT_MIN_SIZE - 1]),
+            Err(DripError::InvalidOracleAccount.into())
+        );
+    }
+
+    #[test_case(100, 160, false)]
+    #[test_case(100, 161, true)]
+    #[test_case(100, 50, false)]
+    fn is_stale_tests(publish_time: i64, now: i64, expected: bool) {
+        let oracle_price = OraclePrice {
+            price: 1,
+            expo: 0,
+            publish_time,
+        };
+        assert_eq!(oracle_price.is_stale(now), expected);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::test_account_size;
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 
 #[account]
 #[derive(Default)]
@@ -9,10 +10,11 @@ pub struct Position {
     // The position authority NFT mint
     pub position_authority: Pubkey, // 32
     // The address to send referral fees too
+    // The referrer's wallet if has_wallet_referrer, otherwise the token b account itself
     pub referrer: Pubkey, // 32
     // Total deposited
     pub deposited_token_a_amount: u64, // 8
-    // Total withdrawn B (amount sent to the user + amount sent to the treasury)
+    // Total withdrawn B since drip_period_id_before_deposit (amount sent to the user + amount sent to the treasury)
     pub withdrawn_token_b_amount: u64, // 8
     pub deposit_timestamp: i64,        // 8
     // The drip period ID of the vault that happened prior to the user opening this position
@@ -24,11 +26,17 @@ pub struct Position {
     // DEPRECATED FIELD: Position accounts are closed now instead of being marked closed
     pub is_closed: bool, // 1
     pub bump: u8,        // 1
+    // Token B spreads in effect when the position was opened, only set if has_recorded_spreads
+    pub token_b_withdrawal_spread: u16, // 2
+    pub token_b_referral_spread: u16,   // 2
+    pub has_recorded_spreads: bool,     // 1
+    // Referral fees go to the token b ATA of the referrer wallet
+    pub has_wallet_referrer: bool, // 1
 }
 
 impl Position {
-    // total space -> 146
-    // allocation needed: ceil( (146+8)/8 )*8 -> 160
+    // total space -> 152
+    // allocation needed: ceil( (152+8)/8 )*8 -> 160
     pub const ACCOUNT_SPACE: usize = 160;
 
     pub fn init(
@@ -52,7 +60,39 @@ impl Position {
         self.periodic_drip_amount = periodic_drip_amount;
         self.is_closed = false;
         self.referrer = referrer;
+        self.has_wallet_referrer = true;
         self.bump = bump;
+        self.token_b_withdrawal_spread = 0;
+        self.token_b_referral_spread = 0;
+        self.has_recorded_spreads = false;
+    }
+
+    pub fn set_referrer(&mut self, referrer: Pubkey) {
+        self.referrer = referrer;
+        self.has_wallet_referrer = true;
+    }
+
+    // Positions opened before wallet referrers stored the referrer token account directly
+    pub fn get_referrer_token_account(
+        &self,
+        token_b_mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        if self.has_wallet_referrer {
+            get_associated_token_address_with_program_id(
+                &self.referrer,
+                token_b_mint,
+                token_program,
+            )
+        } else {
+            self.referrer
+        }
+    }
+
+    pub fn record_spreads(&mut self, token_b_withdrawal_spread: u16, token_b_referral_spread: u16) {
+        self.token_b_withdrawal_spread = token_b_withdrawal_spread;
+        self.token_b_referral_spread = token_b_referral_spread;
+        self.has_recorded_spreads = true;
     }
 
     pub fn get_withdrawable_amount_with_max(&self, max_withdrawable_token_b_amount: u64) -> u64 {
@@ -61,6 +101,24 @@ impl Position {
             .unwrap()
     }
 
+    // Restarts the position's drip schedule from the vault's last drip period
+    // Any token B accrued before this must be withdrawn first
+    pub fn reset_drip_schedule(
+        &mut self,
+        last_drip_period: u64,
+        number_of_swaps: u64,
+        periodic_drip_amount: u64,
+    ) {
+        self.drip_period_id_before_deposit = last_drip_period;
+        self.number_of_swaps = number_of_swaps;
+        self.periodic_drip_amount = periodic_drip_amount;
+        self.withdrawn_token_b_amount = 0;
+    }
+
+    pub fn increase_deposited_amount(&mut self, amount: u64) {
+        self.deposited_token_a_amount = self.deposited_token_a_amount.checked_add(amount).unwrap();
+    }
+
     pub fn increase_withdrawn_amount(&mut self, amount: u64) {
         self.withdrawn_token_b_amount = self.withdrawn_token_b_amount.checked_add(amount).unwrap();
     }
@@ -0,0 +1,59 @@
+use crate::test_account_size;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct Referrer {
+    // Account relations
+    pub vault_proto_config: Pubkey, // 32
+    // The referrer wallet, positions referred by it store it as their referrer
+    pub referrer: Pubkey, // 32
+
+    // Data
+    // Token b withdrawn by referred positions, before spreads
+    pub referred_token_b_volume: u64, // 8
+    // Token b sent to the referrer by referred positions
+    pub earned_token_b_amount: u64, // 8
+    // Used instead of the proto config's token_b_referral_spread for referred positions,
+    // including positions that withdraw at their recorded spreads
+    pub token_b_referral_spread: u16, // 2
+    pub bump: u8,                     // 1
+}
+
+impl Referrer {
+    // total space -> 83
+    // allocation needed: ceil( (83+8)/8 )*8 -> 96
+    pub const ACCOUNT_SPACE: usize = 96;
+
+    pub fn init(
+        &mut self,
+        vault_proto_config: Pubkey,
+        referrer: Pubkey,
+        token_b_referral_spread: u16,
+        bump: u8,
+    ) {
+        self.vault_proto_config = vault_proto_config;
+        self.referrer = referrer;
+        self.referred_token_b_volume = 0;
+        self.earned_token_b_amount = 0;
+        self.token_b_referral_spread = token_b_referral_spread;
+        self.bump = bump;
+    }
+
+    pub fn set_token_b_referral_spread(&mut self, token_b_referral_spread: u16) {
+        self.token_b_referral_spread = token_b_referral_spread;
+    }
+
+    pub fn record_referral(&mut self, withdrawn_token_b_amount: u64, referral_amount: u64) {
+        self.referred_token_b_volume = self
+            .referred_token_b_volume
+            .checked_add(withdrawn_token_b_amount)
+            .unwrap();
+        self.earned_token_b_amount = self
+            .earned_token_b_amount
+            .checked_add(referral_amount)
+            .unwrap();
+    }
+}
+
+test_account_size!(Referrer);
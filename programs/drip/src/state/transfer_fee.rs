@@ -0,0 +1,90 @@
+use crate::errors::DripError;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+
+// Transfer fee withheld from a transfer of amount, 0 for mints without the transfer fee extension
+pub fn calculate_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or_else(|| DripError::InvalidTransferFee.into()),
+        None => Ok(0),
+    }
+}
+
+// Transfer fee withheld from a transfer that delivered post_fee_amount
+pub fn calculate_inverse_transfer_fee(mint: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
+            .ok_or_else(|| DripError::InvalidTransferFee.into()),
+        None => Ok(0),
+    }
+}
+
+fn get_transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    unpack_transfer_fee_config(&mint.try_borrow_data()?)
+}
+
+fn unpack_transfer_fee_config(data: &[u8]) -> Result<Option<TransferFeeConfig>> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::program_option::COption;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+    use spl_token_2022::state::Mint;
+
+    // Local stand-in for a token-2022 mint with a transfer fee
+    pub fn new_transfer_fee_mint_data(transfer_fee_basis_points: u16, maximum_fee: u64) -> Vec<u8> {
+        let len =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+                .unwrap();
+        let mut data = vec![0; len];
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.newer_transfer_fee.transfer_fee_basis_points = transfer_fee_basis_points.into();
+        config.newer_transfer_fee.maximum_fee = maximum_fee.into();
+        config.older_transfer_fee = config.newer_transfer_fee;
+        mint.base = Mint {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn unpack_transfer_fee_config_tests() {
+        let data = new_transfer_fee_mint_data(100, 5);
+        let config = unpack_transfer_fee_config(&data).unwrap().unwrap();
+        assert_eq!(config.calculate_epoch_fee(0, 100), Some(1));
+        assert_eq!(config.calculate_epoch_fee(0, 10_000), Some(5));
+        assert_eq!(config.calculate_inverse_epoch_fee(0, 99), Some(1));
+
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                decimals: 6,
+                is_initialized: true,
+                ..Default::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+        assert_eq!(unpack_transfer_fee_config(&data).unwrap(), None);
+    }
+}
@@ -1,18 +1,30 @@
-use crate::math::calculate_drip_activation_timestamp;
+use crate::math::{calculate_drip_activation_timestamp, is_within_drip_execution_window};
 use crate::state::traits::PDA;
 use crate::state::VaultPeriod;
 use crate::test_account_size;
 use anchor_lang::prelude::*;
 
 pub const VAULT_SWAP_WHITELIST_SIZE: usize = 5;
+pub const VAULT_KEEPER_WHITELIST_SIZE: usize = 2;
 pub const MAX_SLIPPAGE_LOWER_LIMIT_EXCLUSIVE: u16 = 0;
 pub const MAX_SLIPPAGE_UPPER_LIMIT_EXCLUSIVE: u16 = 10_000;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissedDripPolicy {
+    // Missed periods are dropped, the next drip is scheduled from the time of the drip
+    // and positions finish later
+    #[default]
+    Skip,
+    // Missed periods stay owed, the next drip is scheduled one granularity after the
+    // previous activation so keepers can drip the backlog
+    CatchUp,
+}
+
 #[account]
 #[derive(Default, Debug)]
 pub struct Vault {
-    // total space -> 378
-    // allocation needed: ceil( (378+8)/8 )*8 -> 392
+    // total space -> 490
+    // allocation needed: ceil( (490+8)/8 )*8 -> 504
 
     // Account relations
     pub proto_config: Pubkey,                                   // 32
@@ -31,12 +43,29 @@ pub struct Vault {
     pub bump: u8,                       // 1
     pub limit_swaps: bool,              // 1
     pub max_slippage_bps: u16,          // 2
+    // Drips are rejected while the vault is paused
+    pub paused: bool, // 1
+    // Only applied while the vault is paused
+    pub deposits_paused: bool,    // 1
+    pub withdrawals_paused: bool, // 1
+    // Price of token a in token b, drips are checked against it if set
+    pub oracle: Pubkey, // 32
+    // Max deviation of the realized drip price from the oracle price
+    pub max_oracle_deviation_bps: u16, // 2
+    // Mint decimals, needed to convert the oracle price to base units
+    pub token_a_decimals: u8, // 1
+    pub token_b_decimals: u8, // 1
+    // Only these signers can trigger drips, anyone can if it is empty
+    pub whitelisted_keepers: [Pubkey; VAULT_KEEPER_WHITELIST_SIZE], // 32*2
+    // Seconds after the start of each period during which the vault can be dripped, 0 for no limit
+    pub drip_execution_window: u64,           // 8
+    pub missed_drip_policy: MissedDripPolicy, // 1
 }
 
 impl Vault {
-    // total space -> 378
-    // allocation needed: ceil( (378+8)/8 )*8 -> 392
-    pub const ACCOUNT_SPACE: usize = 392;
+    // total space -> 490
+    // allocation needed: ceil( (490+8)/8 )*8 -> 504
+    pub const ACCOUNT_SPACE: usize = 504;
 
     pub fn init(
         &mut self,
@@ -89,8 +118,30 @@ impl Vault {
 
         // snap to a timestamp for this granularity, either now or in the future
         let now = Clock::get().unwrap().unix_timestamp;
-        self.drip_activation_timestamp =
-            calculate_drip_activation_timestamp(now, granularity, true);
+        self.drip_activation_timestamp = match self.missed_drip_policy {
+            MissedDripPolicy::CatchUp if self.drip_amount > 0 => self
+                .drip_activation_timestamp
+                .checked_add(granularity as i64)
+                .expect("drip_activation_timestamp process_drip add"),
+            _ => calculate_drip_activation_timestamp(now, granularity, true),
+        };
+    }
+
+    // An idle vault owes no drips, so its schedule restarts from the current period
+    pub fn restart_drip_schedule_if_idle(&mut self, granularity: u64) {
+        self.restart_drip_schedule_if_idle_at(Clock::get().unwrap().unix_timestamp, granularity);
+    }
+
+    pub fn restart_drip_schedule_if_idle_at(&mut self, timestamp: i64, granularity: u64) {
+        if self.drip_amount == 0 {
+            self.drip_activation_timestamp =
+                self.drip_activation_timestamp
+                    .max(calculate_drip_activation_timestamp(
+                        timestamp,
+                        granularity,
+                        false,
+                    ));
+        }
     }
 
     pub fn set_whitelisted_swaps(&mut self, whitelisted_swaps: Vec<Pubkey>) {
@@ -101,10 +152,72 @@ impl Vault {
         }
     }
 
+    pub fn set_whitelisted_keepers(&mut self, whitelisted_keepers: Vec<Pubkey>) {
+        self.whitelisted_keepers = Default::default();
+        for (i, &keeper) in whitelisted_keepers.iter().enumerate() {
+            self.whitelisted_keepers[i] = keeper;
+        }
+    }
+
+    pub fn is_keeper_allowed(&self, keeper: &Pubkey) -> bool {
+        self.whitelisted_keepers
+            .iter()
+            .all(|whitelisted_keeper| *whitelisted_keeper == Pubkey::default())
+            || self.whitelisted_keepers.contains(keeper)
+    }
+
+    pub fn set_drip_schedule(
+        &mut self,
+        drip_execution_window: u64,
+        missed_drip_policy: MissedDripPolicy,
+        granularity: u64,
+    ) {
+        self.drip_execution_window = drip_execution_window;
+        self.missed_drip_policy = missed_drip_policy;
+
+        // Drips missed before the policy change are not owed
+        let now = Clock::get().unwrap().unix_timestamp;
+        self.drip_activation_timestamp = self
+            .drip_activation_timestamp
+            .max(calculate_drip_activation_timestamp(now, granularity, false));
+    }
+
+    pub fn set_paused(&mut self, paused: bool, pause_deposits: bool, pause_withdrawals: bool) {
+        self.paused = paused;
+        self.deposits_paused = paused && pause_deposits;
+        self.withdrawals_paused = paused && pause_withdrawals;
+    }
+
+    pub fn set_oracle(
+        &mut self,
+        oracle: Pubkey,
+        max_oracle_deviation_bps: u16,
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+    ) {
+        self.oracle = oracle;
+        self.max_oracle_deviation_bps = max_oracle_deviation_bps;
+        self.token_a_decimals = token_a_decimals;
+        self.token_b_decimals = token_b_decimals;
+    }
+
+    pub fn has_oracle(&self) -> bool {
+        self.oracle != Pubkey::default()
+    }
+
     pub fn is_drip_activated(&self) -> bool {
         let now = Clock::get().unwrap().unix_timestamp;
         now >= self.drip_activation_timestamp
     }
+
+    pub fn is_within_drip_execution_window(&self, granularity: u64) -> bool {
+        is_within_drip_execution_window(
+            Clock::get().unwrap().unix_timestamp,
+            self.drip_activation_timestamp,
+            granularity,
+            self.drip_execution_window,
+        )
+    }
 }
 
 impl PDA for Vault {
@@ -123,3 +236,27 @@ impl PDA for Vault {
 }
 
 test_account_size!(Vault);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, 100, 1_000, 960; "Restarts an idle vault from the current period")]
+    #[test_case(0, 2_000, 1_000, 2_000; "Keeps an idle vault's activation that is still in the future")]
+    #[test_case(50, 100, 1_000, 100; "Keeps the schedule of a vault that still drips")]
+    fn restart_drip_schedule_if_idle_at_tests(
+        drip_amount: u64,
+        drip_activation_timestamp: i64,
+        timestamp: i64,
+        expected: i64,
+    ) {
+        let mut vault = Vault {
+            drip_amount,
+            drip_activation_timestamp,
+            ..Vault::default()
+        };
+        vault.restart_drip_schedule_if_idle_at(timestamp, 60);
+        assert_eq!(vault.drip_activation_timestamp, expected);
+    }
+}
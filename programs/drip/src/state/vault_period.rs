@@ -1,6 +1,9 @@
-use crate::math::{calculate_new_twap_amount, compute_price};
+use crate::math::{
+    calculate_new_twap_amount, calculate_time_weighted_price_cumulative, compute_price,
+};
 use crate::test_account_size;
 use anchor_lang::prelude::*;
+use std::convert::TryFrom;
 
 #[account]
 #[derive(Default)]
@@ -19,12 +22,24 @@ pub struct VaultPeriod {
     pub drip_timestamp: i64, //8
     // Bump
     pub bump: u8, // 1
+    // Sum of each drip's price weighted by the seconds since the previous drip, wraps on overflow.
+    // Only the difference between two periods dripped after this field was added is meaningful
+    pub time_weighted_price_cumulative: u128, // 16
+    // Sum over all drips up to this period of token b received per token a dripped
+    // (including the drip trigger spread), a position's token b is its periodic drip amount
+    // times the difference between two periods
+    pub b_per_a_cumulative: u128, // 16
+    // False for periods created before b_per_a_cumulative existed, positions starting at
+    // these periods are still paid out from the twap
+    pub has_b_per_a_cumulative: bool, // 1
 }
 
 impl VaultPeriod {
-    // total space -> 73
-    // allocation needed: ceil( (73+8)/8 )*8 -> 88
-    pub const ACCOUNT_SPACE: usize = 88;
+    // total space -> 106
+    // allocation needed: ceil( (106+8)/8 )*8 -> 120
+    pub const ACCOUNT_SPACE: usize = 120;
+    // Allocated on top of ACCOUNT_SPACE so new fields don't need another realloc
+    pub const RESERVED_SPACE: usize = 64;
 
     pub fn init(&mut self, vault: Pubkey, period_id: u64, bump: u8) {
         self.vault = vault;
@@ -33,6 +48,9 @@ impl VaultPeriod {
         self.dar = 0;
         self.drip_timestamp = 0;
         self.bump = bump;
+        self.time_weighted_price_cumulative = 0;
+        self.b_per_a_cumulative = 0;
+        self.has_b_per_a_cumulative = true;
     }
 
     pub fn increase_drip_amount_to_reduce(&mut self, extra_drip: u64) {
@@ -48,9 +66,35 @@ impl VaultPeriod {
         last_period: &Account<VaultPeriod>,
         sent_a: u64,
         received_b: u64,
+        granularity: u64,
     ) {
         let price = compute_price(received_b, sent_a);
         self.twap = calculate_new_twap_amount(last_period.twap, self.period_id, price);
+
+        // The first drip of a vault has no previous drip, it is weighted as a full period
+        let elapsed_seconds = if last_period.drip_timestamp == 0 {
+            granularity
+        } else {
+            let now = Clock::get().unwrap().unix_timestamp;
+            u64::try_from(now.saturating_sub(last_period.drip_timestamp)).unwrap()
+        };
+        self.time_weighted_price_cumulative = calculate_time_weighted_price_cumulative(
+            last_period.time_weighted_price_cumulative,
+            price,
+            elapsed_seconds,
+        );
+    }
+
+    pub fn update_b_per_a_cumulative(
+        &mut self,
+        last_period: &Account<VaultPeriod>,
+        dripped_a: u64,
+        received_b: u64,
+    ) {
+        self.b_per_a_cumulative = last_period
+            .b_per_a_cumulative
+            .checked_add(compute_price(received_b, dripped_a))
+            .unwrap();
     }
 
     pub fn update_drip_timestamp(&mut self) {
@@ -1,7 +1,43 @@
+use crate::errors::DripError;
 use crate::test_account_size;
+use crate::validate;
 use anchor_lang::prelude::*;
 
 pub const MAX_TOKEN_SPREAD_EXCLUSIVE: u16 = 5_000;
+// Cap on the sum of the drip trigger, withdrawal and referral spreads
+pub const MAX_TOTAL_SPREAD_EXCLUSIVE: u16 = 5_000;
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+// Minimum notice given to open positions before new spreads take effect
+pub const MIN_SPREAD_UPDATE_NOTICE_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WithdrawalSpreadPolicy {
+    // Withdrawals use the token b spreads currently in effect
+    #[default]
+    Current,
+    // Withdrawals use the token b spreads that were in effect when the position was opened
+    AtDeposit,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeeperRewardType {
+    // token_a_drip_trigger_spread of each drip is sent to the keeper in token a
+    #[default]
+    TokenA,
+    // token_a_drip_trigger_spread of the swap output is sent to the keeper in token b
+    TokenB,
+    // keeper_reward_lamports is paid to the keeper out of the vault's sol reserve,
+    // the reserve being the vault's lamports above rent exemption
+    Lamports,
+}
+
+// How a vault proto config rewards drip keepers, fixed at init
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeeperReward {
+    pub reward_type: KeeperRewardType,
+    // only used with KeeperRewardType::Lamports
+    pub lamports: u64,
+}
 
 #[account]
 #[derive(Default)]
@@ -15,12 +51,91 @@ pub struct VaultProtoConfig {
     pub token_b_referral_spread: u16, //2
     // to be used with the vault to modify certain fields (whitelist)
     pub admin: Pubkey, //32
+    // admin proposed by the current admin, becomes the admin once they accept
+    pub pending_admin: Pubkey, //32
+    // spreads that replace the current spreads at pending_spreads_effective_timestamp
+    pub pending_token_a_drip_trigger_spread: u16, // 2
+    pub pending_token_b_withdrawal_spread: u16,   // 2
+    pub pending_token_b_referral_spread: u16,     // 2
+    // 0 if there are no pending spreads
+    pub pending_spreads_effective_timestamp: i64, // 8
+    pub withdrawal_spread_policy: WithdrawalSpreadPolicy, // 1
+    // fixed at init, positions' token b accounting depends on it
+    pub keeper_reward_type: KeeperRewardType, // 1
+    // only used with KeeperRewardType::Lamports
+    pub keeper_reward_lamports: u64, // 8
 }
 
 impl VaultProtoConfig {
-    // total space -> 56
-    // allocation needed: ceil( (46+8)/8 )*8 -> 56
-    pub const ACCOUNT_SPACE: usize = 56;
+    // total space -> 102
+    // allocation needed: ceil( (102+8)/8 )*8 -> 112
+    pub const ACCOUNT_SPACE: usize = 112;
+
+    // Pure validation of the config parameters so that clients can check them before submitting
+    pub fn validate_params(
+        granularity: u64,
+        token_a_drip_trigger_spread: u16,
+        token_b_withdrawal_spread: u16,
+        token_b_referral_spread: u16,
+        calendar_aligned: bool,
+    ) -> Result<()> {
+        validate!(granularity > 0, DripError::InvalidGranularity);
+        // Calendar aligned configs need every day to start on a drip period boundary
+        validate!(
+            !calendar_aligned || SECONDS_PER_DAY.checked_rem(granularity) == Some(0),
+            DripError::InvalidGranularity
+        );
+        VaultProtoConfig::validate_spreads(
+            token_a_drip_trigger_spread,
+            token_b_withdrawal_spread,
+            token_b_referral_spread,
+        )
+    }
+
+    pub fn validate_spreads(
+        token_a_drip_trigger_spread: u16,
+        token_b_withdrawal_spread: u16,
+        token_b_referral_spread: u16,
+    ) -> Result<()> {
+        validate!(
+            token_a_drip_trigger_spread < MAX_TOKEN_SPREAD_EXCLUSIVE
+                && token_b_withdrawal_spread < MAX_TOKEN_SPREAD_EXCLUSIVE
+                && token_b_referral_spread < MAX_TOKEN_SPREAD_EXCLUSIVE,
+            DripError::InvalidSpread
+        );
+        // Each spread is below MAX_TOKEN_SPREAD_EXCLUSIVE so the sum can't overflow a u16
+        validate!(
+            token_a_drip_trigger_spread + token_b_withdrawal_spread + token_b_referral_spread
+                < MAX_TOTAL_SPREAD_EXCLUSIVE,
+            DripError::InvalidTotalSpread
+        );
+        Ok(())
+    }
+
+    // Largest referral spread that keeps the total below MAX_TOTAL_SPREAD_EXCLUSIVE, used for
+    // referral spreads that were validated against spreads that have since changed
+    pub fn clamp_referral_spread(
+        token_a_drip_trigger_spread: u16,
+        token_b_withdrawal_spread: u16,
+        token_b_referral_spread: u16,
+    ) -> u16 {
+        let max_referral_spread = (MAX_TOTAL_SPREAD_EXCLUSIVE - 1)
+            .saturating_sub(token_a_drip_trigger_spread)
+            .saturating_sub(token_b_withdrawal_spread);
+        token_b_referral_spread.min(max_referral_spread)
+    }
+
+    pub fn validate_keeper_reward(
+        keeper_reward_type: KeeperRewardType,
+        keeper_reward_lamports: u64,
+    ) -> Result<()> {
+        // A lamport bounty is only meaningful (and only allowed) in lamports mode
+        validate!(
+            (keeper_reward_type == KeeperRewardType::Lamports) == (keeper_reward_lamports > 0),
+            DripError::InvalidKeeperReward
+        );
+        Ok(())
+    }
 
     pub fn init(
         &mut self,
@@ -29,13 +144,109 @@ impl VaultProtoConfig {
         base_withdrawal_spread: u16,
         token_b_referral_spread: u16,
         admin: Pubkey,
+        keeper_reward: KeeperReward,
     ) {
         self.granularity = granularity;
         self.token_a_drip_trigger_spread = drip_trigger_spread;
         self.token_b_withdrawal_spread = base_withdrawal_spread;
         self.token_b_referral_spread = token_b_referral_spread;
         self.admin = admin;
+        self.pending_admin = Pubkey::default();
+        self.keeper_reward_type = keeper_reward.reward_type;
+        self.keeper_reward_lamports = keeper_reward.lamports;
+    }
+
+    pub fn propose_admin(&mut self, pending_admin: Pubkey) {
+        self.pending_admin = pending_admin;
+    }
+
+    pub fn accept_admin(&mut self) {
+        self.admin = self.pending_admin;
+        self.pending_admin = Pubkey::default();
+    }
+
+    pub fn schedule_spreads(
+        &mut self,
+        token_a_drip_trigger_spread: u16,
+        token_b_withdrawal_spread: u16,
+        token_b_referral_spread: u16,
+        effective_timestamp: i64,
+        withdrawal_spread_policy: WithdrawalSpreadPolicy,
+    ) {
+        // Apply the previously scheduled spreads if they are already in effect
+        let now = Clock::get().unwrap().unix_timestamp;
+        let (drip_trigger_spread, withdrawal_spread, referral_spread) = self.get_spreads_at(now);
+        self.token_a_drip_trigger_spread = drip_trigger_spread;
+        self.token_b_withdrawal_spread = withdrawal_spread;
+        self.token_b_referral_spread = referral_spread;
+
+        self.pending_token_a_drip_trigger_spread = token_a_drip_trigger_spread;
+        self.pending_token_b_withdrawal_spread = token_b_withdrawal_spread;
+        self.pending_token_b_referral_spread = token_b_referral_spread;
+        self.pending_spreads_effective_timestamp = effective_timestamp;
+        self.withdrawal_spread_policy = withdrawal_spread_policy;
+    }
+
+    // Returns (token_a_drip_trigger_spread, token_b_withdrawal_spread, token_b_referral_spread)
+    pub fn get_spreads_at(&self, timestamp: i64) -> (u16, u16, u16) {
+        if self.pending_spreads_effective_timestamp != 0
+            && timestamp >= self.pending_spreads_effective_timestamp
+        {
+            (
+                self.pending_token_a_drip_trigger_spread,
+                self.pending_token_b_withdrawal_spread,
+                self.pending_token_b_referral_spread,
+            )
+        } else {
+            (
+                self.token_a_drip_trigger_spread,
+                self.token_b_withdrawal_spread,
+                self.token_b_referral_spread,
+            )
+        }
+    }
+
+    pub fn get_spreads(&self) -> (u16, u16, u16) {
+        // The clock only matters once new spreads have been scheduled
+        if self.pending_spreads_effective_timestamp == 0 {
+            return self.get_spreads_at(0);
+        }
+        self.get_spreads_at(Clock::get().unwrap().unix_timestamp)
+    }
+
+    // The drip trigger spread is only taken out of token a when the keeper is paid in token a,
+    // otherwise the full drip amount is swapped
+    pub fn get_token_a_swap_spread(&self) -> u16 {
+        match self.keeper_reward_type {
+            KeeperRewardType::TokenA => self.get_spreads().0,
+            KeeperRewardType::TokenB | KeeperRewardType::Lamports => 0,
+        }
     }
 }
 
 test_account_size!(VaultProtoConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(10, 10, 100, 100; "Keeps referral spreads within the cap")]
+    #[test_case(2000, 2000, 1000, 999; "Caps the referral spread below the total")]
+    #[test_case(4999, 4999, 10, 0; "Returns 0 when the other spreads exceed the cap")]
+    fn clamp_referral_spread_tests(
+        token_a_drip_trigger_spread: u16,
+        token_b_withdrawal_spread: u16,
+        token_b_referral_spread: u16,
+        expected: u16,
+    ) {
+        assert_eq!(
+            VaultProtoConfig::clamp_referral_spread(
+                token_a_drip_trigger_spread,
+                token_b_withdrawal_spread,
+                token_b_referral_spread
+            ),
+            expected
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use crate::state::{Vault, VaultPeriod, VaultProtoConfig};
+use crate::state::{
+    KeeperRewardType, MissedDripPolicy, Position, Vault, VaultPeriod, VaultProtoConfig,
+    WithdrawalSpreadPolicy,
+};
+use crate::{DecreasePositionAccounts, IncreasePositionAccounts, WithdrawCommonAccounts};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_lang::{
@@ -11,6 +15,7 @@ use anchor_lang::{
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use lazy_static::lazy_static;
 use spl_token::state::AccountState;
+use std::convert::TryFrom;
 
 #[account]
 #[derive(Default)]
@@ -89,6 +94,21 @@ where
         Self::new_program_data_account(Token::id(), state, key)
     }
 
+    // Anchor doesn't serialize spl token state, so it is packed into the account data instead
+    pub fn new_packed_token_program_account<State: Pack>(
+        state: State,
+        key: Option<Pubkey>,
+    ) -> Self {
+        let mut account = Self::new_system_account(key);
+        account.owner = Token::id();
+
+        let mut buf = vec![0; State::LEN];
+        State::pack(state, &mut buf).unwrap();
+        account.data = buf;
+
+        account
+    }
+
     pub fn to_account_info(&mut self) -> AccountInfo {
         AccountInfo::new(
             &self.key,
@@ -103,6 +123,314 @@ where
     }
 }
 
+pub fn new_token_account(
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+    key: Option<Pubkey>,
+) -> AccountFixture<TokenAccount> {
+    AccountFixture::new_packed_token_program_account(
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        key,
+    )
+}
+
+pub fn new_mint(key: Option<Pubkey>) -> AccountFixture<Mint> {
+    AccountFixture::new_packed_token_program_account(
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: 1_000_000_000_000_000, // 1 billion
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        key,
+    )
+}
+
+pub fn new_vault_period(period_id: u64, dar: u64) -> VaultPeriod {
+    VaultPeriod {
+        period_id,
+        dar,
+        ..VaultPeriod::try_deserialize(&mut VAULT_PERIOD_0.data.as_slice()).unwrap()
+    }
+}
+
+// Fixtures for withdrawing from user_position in VAULT, signed by the holder of the position NFT
+pub struct WithdrawCommonFixtures {
+    pub withdrawer: AccountFixture<NoData>,
+    pub vault: AccountFixture<Vault>,
+    pub vault_proto_config: AccountFixture<VaultProtoConfig>,
+    pub vault_period_i: AccountFixture<VaultPeriod>,
+    pub vault_period_j: AccountFixture<VaultPeriod>,
+    pub user_position: AccountFixture<Position>,
+    pub user_position_nft_account: AccountFixture<TokenAccount>,
+    pub vault_token_b_account: AccountFixture<TokenAccount>,
+    pub vault_treasury_token_b_account: AccountFixture<TokenAccount>,
+    pub user_token_b_account: AccountFixture<TokenAccount>,
+    pub referrer: AccountFixture<NoData>,
+    pub token_b_mint: AccountFixture<Mint>,
+    pub token_program: AccountFixture<NoData>,
+}
+
+impl WithdrawCommonFixtures {
+    pub fn new(
+        vault: Vault,
+        vault_period_i: VaultPeriod,
+        vault_period_j: VaultPeriod,
+        user_position: Position,
+    ) -> Self {
+        let withdrawer = AccountFixture::new_signer(None);
+        let user_position_nft_account =
+            new_token_account(user_position.position_authority, withdrawer.key, 1, None);
+        WithdrawCommonFixtures {
+            withdrawer,
+            vault: AccountFixture::new_drip_account(vault, Some(VAULT.key)),
+            vault_proto_config: VAULT_PROTO_CONFIG.clone(),
+            vault_period_i: AccountFixture::new_drip_account(vault_period_i, None),
+            vault_period_j: AccountFixture::new_drip_account(vault_period_j, None),
+            referrer: AccountFixture::new_system_account(Some(user_position.referrer)),
+            user_position: AccountFixture::new_drip_account(user_position, None),
+            user_position_nft_account,
+            vault_token_b_account: new_token_account(
+                TOKEN_B_MINT.key,
+                VAULT.key,
+                0,
+                Some(VAULT_TOKEN_B_ACCOUNT.key),
+            ),
+            vault_treasury_token_b_account: new_token_account(
+                TOKEN_B_MINT.key,
+                ADMIN.key,
+                0,
+                Some(VAULT_TREASURY_TOKEN_B_ACCOUNT.key),
+            ),
+            user_token_b_account: new_token_account(
+                TOKEN_B_MINT.key,
+                Pubkey::new_unique(),
+                0,
+                None,
+            ),
+            token_b_mint: new_mint(Some(TOKEN_B_MINT.key)),
+            token_program: AccountFixture::new_program(Token::id()),
+        }
+    }
+
+    pub fn to_account_infos(&mut self) -> WithdrawCommonAccountInfos<'_> {
+        WithdrawCommonAccountInfos {
+            withdrawer: self.withdrawer.to_account_info(),
+            vault: self.vault.to_account_info(),
+            vault_proto_config: self.vault_proto_config.to_account_info(),
+            vault_period_i: self.vault_period_i.to_account_info(),
+            vault_period_j: self.vault_period_j.to_account_info(),
+            user_position: self.user_position.to_account_info(),
+            user_position_nft_account: self.user_position_nft_account.to_account_info(),
+            vault_token_b_account: self.vault_token_b_account.to_account_info(),
+            vault_treasury_token_b_account: self.vault_treasury_token_b_account.to_account_info(),
+            user_token_b_account: self.user_token_b_account.to_account_info(),
+            referrer: self.referrer.to_account_info(),
+            token_b_mint: self.token_b_mint.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+pub struct WithdrawCommonAccountInfos<'info> {
+    pub withdrawer: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub vault_proto_config: AccountInfo<'info>,
+    pub vault_period_i: AccountInfo<'info>,
+    pub vault_period_j: AccountInfo<'info>,
+    pub user_position: AccountInfo<'info>,
+    pub user_position_nft_account: AccountInfo<'info>,
+    pub vault_token_b_account: AccountInfo<'info>,
+    pub vault_treasury_token_b_account: AccountInfo<'info>,
+    pub user_token_b_account: AccountInfo<'info>,
+    pub referrer: AccountInfo<'info>,
+    pub token_b_mint: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> WithdrawCommonAccountInfos<'info> {
+    pub fn to_accounts(&'info self) -> WithdrawCommonAccounts<'info> {
+        WithdrawCommonAccounts {
+            withdrawer: Signer::try_from(&self.withdrawer).unwrap(),
+            vault: Box::new(Account::try_from(&self.vault).unwrap()),
+            vault_proto_config: Box::new(Account::try_from(&self.vault_proto_config).unwrap()),
+            vault_period_i: Account::try_from(&self.vault_period_i).unwrap(),
+            vault_period_j: Account::try_from(&self.vault_period_j).unwrap(),
+            user_position: Account::try_from(&self.user_position).unwrap(),
+            user_position_nft_account: InterfaceAccount::try_from(&self.user_position_nft_account)
+                .unwrap(),
+            vault_token_b_account: Box::new(
+                InterfaceAccount::try_from(&self.vault_token_b_account).unwrap(),
+            ),
+            vault_treasury_token_b_account: Box::new(
+                InterfaceAccount::try_from(&self.vault_treasury_token_b_account).unwrap(),
+            ),
+            user_token_b_account: Box::new(
+                InterfaceAccount::try_from(&self.user_token_b_account).unwrap(),
+            ),
+            referrer: UncheckedAccount::try_from(&self.referrer),
+            token_b_mint: Box::new(InterfaceAccount::try_from(&self.token_b_mint).unwrap()),
+            token_program: Interface::try_from(&self.token_program).unwrap(),
+            token_b_program: Interface::try_from(&self.token_program).unwrap(),
+            referrer_account: None,
+        }
+    }
+}
+
+// Fixtures for increasing or decreasing a position of number_of_swaps swaps opened after
+// period 0. The vault has dripped up to last_drip_period at a price of 0, so there is no
+// token b to withdraw first
+pub struct UpdatePositionFixtures {
+    pub common: WithdrawCommonFixtures,
+    pub vault_period_user_expiry: AccountFixture<VaultPeriod>,
+    // None if the new expiry period is the user's expiry period
+    pub vault_period_new_expiry: Option<AccountFixture<VaultPeriod>>,
+    pub vault_token_a_account: AccountFixture<TokenAccount>,
+    pub user_token_a_account: AccountFixture<TokenAccount>,
+    pub token_a_mint: AccountFixture<Mint>,
+    pub token_a_program: AccountFixture<NoData>,
+}
+
+impl UpdatePositionFixtures {
+    pub fn new(
+        number_of_swaps: u64,
+        periodic_drip_amount: u64,
+        last_drip_period: u64,
+        new_expiry_period_id: u64,
+    ) -> Self {
+        let deposited_token_a_amount = number_of_swaps * periodic_drip_amount;
+        let is_expired = last_drip_period >= number_of_swaps;
+        let vault = Vault {
+            last_drip_period,
+            drip_amount: if is_expired { 0 } else { periodic_drip_amount },
+            ..Vault::try_deserialize(&mut VAULT.data.as_slice()).unwrap()
+        };
+        let user_position = Position {
+            vault: VAULT.key,
+            position_authority: Pubkey::new_unique(),
+            referrer: Pubkey::new_unique(),
+            deposited_token_a_amount,
+            number_of_swaps,
+            periodic_drip_amount,
+            ..Position::default()
+        };
+
+        UpdatePositionFixtures {
+            common: WithdrawCommonFixtures::new(
+                vault,
+                new_vault_period(0, 0),
+                new_vault_period(last_drip_period.min(number_of_swaps), 0),
+                user_position,
+            ),
+            vault_period_user_expiry: AccountFixture::new_drip_account(
+                new_vault_period(number_of_swaps, periodic_drip_amount),
+                None,
+            ),
+            vault_period_new_expiry: (new_expiry_period_id != number_of_swaps).then(|| {
+                AccountFixture::new_drip_account(new_vault_period(new_expiry_period_id, 0), None)
+            }),
+            vault_token_a_account: new_token_account(
+                TOKEN_A_MINT.key,
+                VAULT.key,
+                deposited_token_a_amount,
+                Some(VAULT_TOKEN_A_ACCOUNT.key),
+            ),
+            user_token_a_account: new_token_account(
+                TOKEN_A_MINT.key,
+                Pubkey::new_unique(),
+                0,
+                None,
+            ),
+            token_a_mint: new_mint(Some(TOKEN_A_MINT.key)),
+            token_a_program: AccountFixture::new_program(Token::id()),
+        }
+    }
+
+    pub fn to_account_infos(&mut self) -> UpdatePositionAccountInfos<'_> {
+        UpdatePositionAccountInfos {
+            common: self.common.to_account_infos(),
+            vault_period_user_expiry: self.vault_period_user_expiry.to_account_info(),
+            vault_period_new_expiry: self
+                .vault_period_new_expiry
+                .as_mut()
+                .map(|vault_period_new_expiry| vault_period_new_expiry.to_account_info()),
+            vault_token_a_account: self.vault_token_a_account.to_account_info(),
+            user_token_a_account: self.user_token_a_account.to_account_info(),
+            token_a_mint: self.token_a_mint.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+        }
+    }
+}
+
+pub struct UpdatePositionAccountInfos<'info> {
+    pub common: WithdrawCommonAccountInfos<'info>,
+    pub vault_period_user_expiry: AccountInfo<'info>,
+    pub vault_period_new_expiry: Option<AccountInfo<'info>>,
+    pub vault_token_a_account: AccountInfo<'info>,
+    pub user_token_a_account: AccountInfo<'info>,
+    pub token_a_mint: AccountInfo<'info>,
+    pub token_a_program: AccountInfo<'info>,
+}
+
+impl<'info> UpdatePositionAccountInfos<'info> {
+    pub fn to_increase_position_accounts(&'info self) -> IncreasePositionAccounts<'info> {
+        IncreasePositionAccounts {
+            common: self.common.to_accounts(),
+            vault_period_user_expiry: Box::new(
+                Account::try_from(&self.vault_period_user_expiry).unwrap(),
+            ),
+            vault_period_new_expiry: Box::new(
+                Account::try_from(self.get_vault_period_new_expiry()).unwrap(),
+            ),
+            vault_token_a_account: Box::new(
+                InterfaceAccount::try_from(&self.vault_token_a_account).unwrap(),
+            ),
+            user_token_a_account: Box::new(
+                InterfaceAccount::try_from(&self.user_token_a_account).unwrap(),
+            ),
+            token_a_mint: Box::new(InterfaceAccount::try_from(&self.token_a_mint).unwrap()),
+            token_a_program: Interface::try_from(&self.token_a_program).unwrap(),
+        }
+    }
+
+    pub fn to_decrease_position_accounts(&'info self) -> DecreasePositionAccounts<'info> {
+        DecreasePositionAccounts {
+            common: self.common.to_accounts(),
+            vault_period_user_expiry: Box::new(
+                Account::try_from(&self.vault_period_user_expiry).unwrap(),
+            ),
+            vault_period_new_expiry: Box::new(
+                Account::try_from(self.get_vault_period_new_expiry()).unwrap(),
+            ),
+            vault_token_a_account: Box::new(
+                InterfaceAccount::try_from(&self.vault_token_a_account).unwrap(),
+            ),
+            user_token_a_account: Box::new(
+                InterfaceAccount::try_from(&self.user_token_a_account).unwrap(),
+            ),
+            token_a_mint: Box::new(InterfaceAccount::try_from(&self.token_a_mint).unwrap()),
+            token_a_program: Interface::try_from(&self.token_a_program).unwrap(),
+        }
+    }
+
+    fn get_vault_period_new_expiry(&self) -> &AccountInfo<'info> {
+        self.vault_period_new_expiry
+            .as_ref()
+            .unwrap_or(&self.vault_period_user_expiry)
+    }
+}
+
 pub fn new_anchor_wrapped_account<
     AnchorWrapperAccount: AccountDeserialize,
     InnerSolanaAccount: Pack,
@@ -129,6 +457,14 @@ lazy_static! {
             token_b_withdrawal_spread: 50,
             token_b_referral_spread: 10,
             admin: ADMIN.key,
+            pending_admin: Pubkey::default(),
+            pending_token_a_drip_trigger_spread: 0,
+            pending_token_b_withdrawal_spread: 0,
+            pending_token_b_referral_spread: 0,
+            pending_spreads_effective_timestamp: 0,
+            withdrawal_spread_policy: WithdrawalSpreadPolicy::Current,
+            keeper_reward_type: KeeperRewardType::TokenA,
+            keeper_reward_lamports: 0,
         }, None);
 
     pub static ref TOKEN_A_MINT: AccountFixture<Mint> = AccountFixture::new_token_program_account(
@@ -207,6 +543,9 @@ lazy_static! {
             twap: 0,
             drip_timestamp: 0,
             bump: 0,
+            time_weighted_price_cumulative: 0,
+            b_per_a_cumulative: 0,
+            has_b_per_a_cumulative: true,
         },
         None
     );
@@ -219,6 +558,9 @@ lazy_static! {
             twap: 0,
             drip_timestamp: 0,
             bump: 0,
+            time_weighted_price_cumulative: 0,
+            b_per_a_cumulative: 0,
+            has_b_per_a_cumulative: true,
         },
         None
     );
@@ -237,5 +579,15 @@ lazy_static! {
         bump: 0,
         limit_swaps: true,
         max_slippage_bps: 1000,
+        paused: false,
+        deposits_paused: false,
+        withdrawals_paused: false,
+        oracle: Pubkey::default(),
+        max_oracle_deviation_bps: 0,
+        token_a_decimals: 0,
+        token_b_decimals: 0,
+        whitelisted_keepers: [Pubkey::default(), Pubkey::default()],
+        drip_execution_window: 0,
+        missed_drip_policy: MissedDripPolicy::Skip,
     }, None);
 }